@@ -0,0 +1,65 @@
+//! Benchmarks for the decoding hot path: LZW decompression and conversion
+//! to RGBA. Each runs on a 10-frame and a 100-frame animation cut from
+//! `earth.gif`, to expose costs that grow with the frame count.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gif::{lzw, Gif};
+
+const EARTH: &[u8] = include_bytes!("../earth.gif");
+
+/// Returns `earth.gif` with its frames repeated or cut to `frames` frames.
+fn fixture(frames: usize) -> Gif {
+    let mut gif = Gif::from_bytes(EARTH).unwrap();
+    gif.frames = gif.frames.iter().cycle().take(frames).cloned().collect();
+    gif
+}
+
+fn from_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_bytes");
+    for &frames in &[10, 100] {
+        let bytes = Vec::from(fixture(frames));
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(frames), &bytes, |b, bytes| {
+            b.iter(|| Gif::from_bytes(bytes).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn lzw_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lzw_decode");
+    for &frames in &[10, 100] {
+        let gif = fixture(frames);
+        let data: Vec<_> = gif
+            .frames
+            .iter()
+            .map(|frame| (lzw::encode(8, &frame.pixels), frame.pixels.len()))
+            .collect();
+        let len: usize = data.iter().map(|(data, _)| data.len()).sum();
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(frames), &data, |b, data| {
+            b.iter(|| {
+                for &(ref data, pixel_count) in data {
+                    lzw::decode(8, data, pixel_count).unwrap();
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn to_rgba_frames(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_rgba_frames");
+    for &frames in &[10, 100] {
+        let gif = fixture(frames);
+        let pixels: usize = gif.frames.iter().map(|frame| frame.pixels.len()).sum();
+        group.throughput(Throughput::Elements(pixels as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(frames), &gif, |b, gif| {
+            b.iter(|| gif.to_rgba_frames().unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, from_bytes, lzw_decode, to_rgba_frames);
+criterion_main!(benches);
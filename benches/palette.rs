@@ -0,0 +1,73 @@
+//! Benchmarks `Palette::nearest_color` against the brute-force scan in
+//! `ColorTable::nearest`, and the cost of building the palette, for a
+//! 16-color table and the 256-color table of `earth.gif`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gif::{ColorTable, Gif, Palette};
+
+const EARTH: &[u8] = include_bytes!("../earth.gif");
+
+/// Returns the first `colors` entries of `earth.gif`'s global color table.
+fn table(colors: usize) -> ColorTable {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    let table = gif.global_color_table.unwrap();
+    ColorTable::new(table.iter().take(colors).cloned().collect())
+}
+
+/// Every 16th pixel of `earth.gif`'s first frame, as a quantizer would
+/// look the colors of a true-color image up.
+fn queries() -> Vec<(u8, u8, u8)> {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    let rgba = gif.to_rgba_frames().unwrap().swap_remove(0);
+    rgba.iter()
+        .step_by(16)
+        .map(|pixel| (pixel.red, pixel.green, pixel.blue))
+        .collect()
+}
+
+fn nearest(c: &mut Criterion) {
+    let queries = queries();
+    let mut group = c.benchmark_group("nearest");
+    group.throughput(Throughput::Elements(queries.len() as u64));
+    for &colors in &[16, 256] {
+        let table = table(colors);
+        let palette = Palette::new(table.clone());
+        group.bench_with_input(
+            BenchmarkId::new("palette", colors),
+            &palette,
+            |b, palette| {
+                b.iter(|| {
+                    for &(r, g, bl) in &queries {
+                        criterion::black_box(palette.nearest_color(r, g, bl));
+                    }
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("color_table", colors),
+            &table,
+            |b, table| {
+                b.iter(|| {
+                    for &(r, g, bl) in &queries {
+                        criterion::black_box(table.nearest(r, g, bl));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn palette_new(c: &mut Criterion) {
+    let mut group = c.benchmark_group("palette_new");
+    for &colors in &[16, 256] {
+        let table = table(colors);
+        group.bench_with_input(BenchmarkId::from_parameter(colors), &table, |b, table| {
+            b.iter(|| Palette::new(table.clone()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, nearest, palette_new);
+criterion_main!(benches);
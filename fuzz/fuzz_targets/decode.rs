@@ -0,0 +1,9 @@
+#![no_main]
+
+use gif::Gif;
+use libfuzzer_sys::fuzz_target;
+
+//any input must decode or fail with an error; a panic is a bug.
+fuzz_target!(|data: &[u8]| {
+    let _ = Gif::from_bytes(data);
+});
@@ -0,0 +1,22 @@
+#![no_main]
+
+use gif::lzw;
+use libfuzzer_sys::fuzz_target;
+
+//the first byte picks a minimum code size from 2 to 8 and the rest are the
+//pixels, so every input is valid and must survive encoding unchanged.
+fuzz_target!(|data: &[u8]| {
+    let (min_code_size, pixels) = match data.split_first() {
+        Some((&first, rest)) => (2 + first % 7, rest),
+        None => return,
+    };
+    let pixels: Vec<u8> = pixels
+        .iter()
+        .map(|&pixel| (pixel as u16 & ((1 << min_code_size) - 1)) as u8)
+        .collect();
+
+    let encoded = lzw::encode(min_code_size, &pixels);
+    let decoded = lzw::decode(min_code_size, &encoded, pixels.len())
+        .expect("encoded data failed to decode");
+    assert_eq!(decoded, pixels);
+});
@@ -0,0 +1,218 @@
+use alloc::vec::Vec;
+
+use crate::color::TRANSPARENT_BLACK_RGBA;
+use crate::{DisposalMethod, Gif, GifError};
+
+/// Composites the frames of an animation onto a canvas, honouring each
+/// frame's disposal method.
+///
+/// The canvas starts out fully transparent. Rendering frames in increasing
+/// order only draws the frames in between; going backwards replays the
+/// animation from the first frame.
+pub struct Animator<'a> {
+    gif: &'a Gif,
+    compositor: Compositor,
+}
+
+impl<'a> Animator<'a> {
+    /// Creates an animator positioned before the first frame of `gif`.
+    pub fn new(gif: &'a Gif) -> Animator<'a> {
+        Animator {
+            gif,
+            compositor: Compositor::new(gif),
+        }
+    }
+
+    /// Returns the RGBA canvas as it appears while the frame at `index` is
+    /// displayed.
+    ///
+    /// Frames whose colors can't be resolved are left out of the composite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the number of frames.
+    pub fn render_frame(&mut self, index: usize) -> &[u8] {
+        assert!(index < self.gif.frames.len(), "frame index out of range");
+
+        if index + 1 < self.compositor.next {
+            self.compositor = Compositor::new(self.gif);
+        }
+        while self.compositor.next <= index {
+            self.compositor.step(self.gif).ok();
+        }
+        &self.compositor.canvas
+    }
+}
+
+/// Composites frames like [`Animator`], but fails rather than leaving out a
+/// frame that can't be drawn, and hands back a copy of the canvas.
+///
+/// Seeking forwards only draws the frames in between, so stepping through
+/// the frames one at a time composites each of them once.
+pub struct GifSeeker<'a> {
+    gif: &'a Gif,
+    compositor: Compositor,
+}
+
+impl<'a> GifSeeker<'a> {
+    /// Creates a seeker positioned before the first frame of `gif`.
+    pub fn new(gif: &'a Gif) -> GifSeeker<'a> {
+        GifSeeker {
+            gif,
+            compositor: Compositor::new(gif),
+        }
+    }
+
+    /// Returns the RGBA canvas as it appears while the frame at `index` is
+    /// displayed, compositing frames `0..=index` as needed.
+    ///
+    /// Fails with [`GifError::OutOfBounds`] if there is no such frame, or
+    /// with the error of the first frame that can't be drawn, after which
+    /// the next seek starts again from the first frame.
+    pub fn seek(&mut self, index: usize) -> Result<Vec<u8>, GifError> {
+        if index >= self.gif.frames.len() {
+            return Err(GifError::OutOfBounds);
+        }
+
+        if index + 1 < self.compositor.next {
+            self.compositor = Compositor::new(self.gif);
+        }
+        while self.compositor.next <= index {
+            if let Err(e) = self.compositor.step(self.gif) {
+                self.compositor = Compositor::new(self.gif);
+                return Err(e);
+            }
+        }
+        Ok(self.compositor.canvas.clone())
+    }
+}
+
+/// An iterator over the composited RGBA canvases of a GIF, one per frame.
+///
+/// Each canvas is composited only when it is asked for.
+pub struct IntoIter {
+    gif: Gif,
+    compositor: Compositor,
+}
+
+impl Iterator for IntoIter {
+    type Item = Result<Vec<u8>, GifError>;
+
+    fn next(&mut self) -> Option<Result<Vec<u8>, GifError>> {
+        if self.compositor.next >= self.gif.frames.len() {
+            return None;
+        }
+        let compositor = &mut self.compositor;
+        Some(
+            compositor
+                .step(&self.gif)
+                .map(|_| compositor.canvas.clone()),
+        )
+    }
+}
+
+impl IntoIterator for Gif {
+    type Item = Result<Vec<u8>, GifError>;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter {
+            compositor: Compositor::new(&self),
+            gif: self,
+        }
+    }
+}
+
+/// The running state of a composite: the canvas, and what's needed to dispose
+/// of the frame drawn last.
+struct Compositor {
+    canvas: Vec<u8>,
+    /// The canvas as it was before the last frame was drawn, kept when that
+    /// frame is to be restored to previous.
+    previous: Option<Vec<u8>>,
+    /// The index of the next frame to draw.
+    next: usize,
+}
+
+impl Compositor {
+    fn new(gif: &Gif) -> Compositor {
+        Compositor {
+            canvas: vec![0; gif.lsd.width as usize * gif.lsd.height as usize * 4],
+            previous: None,
+            next: 0,
+        }
+    }
+
+    /// Disposes of the last frame and draws the next one.
+    ///
+    /// The compositor moves on to the following frame even if this one fails
+    /// to draw.
+    fn step(&mut self, gif: &Gif) -> Result<(), GifError> {
+        let index = self.next;
+        if index > 0 {
+            self.dispose(gif, index - 1);
+        }
+        self.next += 1;
+        self.draw(gif, index)
+    }
+
+    /// Applies the disposal method of the frame at `index`, which was drawn last.
+    fn dispose(&mut self, gif: &Gif, index: usize) {
+        match *gif.frames[index].disposal_method() {
+            DisposalMethod::RestoreToBackground => {
+                let background = background(gif);
+                let width = gif.lsd.width as usize;
+                for (x, y) in frame_area(gif, index) {
+                    let offset = (y * width + x) * 4;
+                    self.canvas[offset..offset + 4].copy_from_slice(&background);
+                }
+            }
+            DisposalMethod::RestoreToPrevious => {
+                if let Some(previous) = self.previous.take() {
+                    self.canvas = previous;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, gif: &Gif, index: usize) -> Result<(), GifError> {
+        if let DisposalMethod::RestoreToPrevious = *gif.frames[index].disposal_method() {
+            self.previous = Some(self.canvas.clone());
+        }
+
+        let rgba = gif.frame_to_rgba(index)?;
+        let descriptor = &gif.frames[index].descriptor;
+        let width = gif.lsd.width as usize;
+        for (x, y) in frame_area(gif, index) {
+            let source = ((y - descriptor.top as usize) * descriptor.width as usize + x
+                - descriptor.left as usize)
+                * 4;
+            //transparent pixels leave the canvas untouched.
+            if rgba[source + 3] != 0 {
+                let offset = (y * width + x) * 4;
+                self.canvas[offset..offset + 4].copy_from_slice(&rgba[source..source + 4]);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the canvas coordinates covered by the frame at `index`, clipped to
+/// the canvas.
+fn frame_area(gif: &Gif, index: usize) -> impl Iterator<Item = (usize, usize)> {
+    let descriptor = &gif.frames[index].descriptor;
+    let left = descriptor.left as usize;
+    let top = descriptor.top as usize;
+    let right = (left + descriptor.width as usize).min(gif.lsd.width as usize);
+    let bottom = (top + descriptor.height as usize).min(gif.lsd.height as usize);
+    (top..bottom).flat_map(move |y| (left..right).map(move |x| (x, y)))
+}
+
+/// The color the canvas is restored to, or transparent if there is none.
+fn background(gif: &Gif) -> [u8; 4] {
+    match gif.background_color() {
+        Some(color) => color.to_rgba(0xff),
+        None => TRANSPARENT_BLACK_RGBA,
+    }
+}
@@ -0,0 +1,49 @@
+use std::io;
+
+use png::{BitDepth, ColorType, Encoder, EncodingError};
+
+use crate::{Animator, Gif, GifError};
+
+/// Encodes every composited frame of `gif` as a full-canvas APNG frame.
+pub(crate) fn encode(gif: &Gif) -> Result<Vec<u8>, GifError> {
+    //PNG has no empty images, and APNG no animations without frames.
+    if gif.frames.is_empty() || gif.lsd.width == 0 || gif.lsd.height == 0 {
+        return Err(GifError::InvalidGifFile);
+    }
+    //GIF counts repeats after the first play, APNG counts plays.
+    let plays = match gif.loop_count {
+        Some(0) => 0,
+        Some(count) => count as u32 + 1,
+        None => 1,
+    };
+
+    let mut bytes = vec![];
+    {
+        let mut encoder = Encoder::new(&mut bytes, gif.lsd.width as u32, gif.lsd.height as u32);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+        encoder
+            .set_animated(gif.frames.len() as u32, plays)
+            .map_err(png_error)?;
+        let mut writer = encoder.write_header().map_err(png_error)?;
+
+        let mut animator = Animator::new(gif);
+        for (index, frame) in gif.frames.iter().enumerate() {
+            writer
+                .set_frame_delay(frame.delay_centiseconds(), 100)
+                .map_err(png_error)?;
+            writer
+                .write_image_data(animator.render_frame(index))
+                .map_err(png_error)?;
+        }
+        writer.finish().map_err(png_error)?;
+    }
+    Ok(bytes)
+}
+
+fn png_error(e: EncodingError) -> GifError {
+    match e {
+        EncodingError::IoError(e) => GifError::Io(e),
+        e => GifError::Io(io::Error::other(e)),
+    }
+}
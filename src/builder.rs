@@ -0,0 +1,200 @@
+use alloc::vec::Vec;
+
+use crate::{
+    ColorTable, DisposalMethod, Frame, Gif, GifError, GifVersion, GraphicControlExtension,
+    ImageDescriptor, LogicalScreenDescriptor,
+};
+
+/// Assembles a [`Gif`] from scratch.
+#[derive(Debug, Clone)]
+pub struct GifBuilder {
+    width: u16,
+    height: u16,
+    global_color_table: Option<ColorTable>,
+    loop_count: Option<u16>,
+    frames: Vec<FrameBuilder>,
+}
+
+/// Assembles a single [`Frame`] for a [`GifBuilder`].
+#[derive(Debug, Clone)]
+pub struct FrameBuilder {
+    left: u16,
+    top: u16,
+    width: u16,
+    height: u16,
+    pixels: Vec<u8>,
+    local_color_table: Option<ColorTable>,
+    graphic_control: Option<GraphicControlExtension>,
+}
+
+impl GifBuilder {
+    /// Starts a GIF with a canvas of `width` by `height` pixels.
+    pub fn new(width: u16, height: u16) -> Self {
+        GifBuilder {
+            width,
+            height,
+            global_color_table: None,
+            loop_count: None,
+            frames: vec![],
+        }
+    }
+
+    /// Sets the global color table. Its first color is the background.
+    pub fn global_color_table(mut self, ct: ColorTable) -> Self {
+        self.global_color_table = Some(ct);
+        self
+    }
+
+    /// Sets how many times the animation repeats; `0` means forever.
+    pub fn loop_count(mut self, count: u16) -> Self {
+        self.loop_count = Some(count);
+        self
+    }
+
+    /// Appends a frame.
+    pub fn add_frame(mut self, frame: FrameBuilder) -> Self {
+        self.frames.push(frame);
+        self
+    }
+
+    /// Builds the GIF, failing with the first problem [`Gif::validate`]
+    /// finds, or if a frame's pixel data doesn't match its size. A color
+    /// table with more than 256 colors fails with
+    /// [`GifError::InvalidColorTable`].
+    pub fn build(self) -> Result<Gif, GifError> {
+        if let Some(ref table) = self.global_color_table {
+            if table.len() > 256 {
+                return Err(GifError::InvalidColorTable);
+            }
+        }
+
+        let lsd = LogicalScreenDescriptor {
+            width: self.width,
+            height: self.height,
+            has_global_color_table: self.global_color_table.is_some(),
+            color_resolution: 8,
+            background_color_index: self.global_color_table.as_ref().map(|_| 0),
+            global_color_table_size_flag: self
+                .global_color_table
+                .as_ref()
+                .map_or(0, ColorTable::size_field),
+            ..LogicalScreenDescriptor::default()
+        };
+
+        let mut frames = Vec::with_capacity(self.frames.len());
+        for (index, frame) in self.frames.into_iter().enumerate() {
+            frames.push(frame.build(index)?);
+        }
+
+        let gif = Gif {
+            version: GifVersion::V89a,
+            lsd,
+            global_color_table: self.global_color_table,
+            image_descriptors: frames
+                .iter()
+                .map(|frame| frame.descriptor.clone())
+                .collect(),
+            frames,
+            loop_count: self.loop_count,
+            comments: vec![],
+            plain_text_extensions: vec![],
+            unknown_extensions: vec![],
+        };
+        match gif.validate() {
+            Ok(()) => Ok(gif),
+            Err(mut errors) => Err(errors.remove(0)),
+        }
+    }
+}
+
+impl FrameBuilder {
+    /// Starts a `width` by `height` frame at the top left of the canvas,
+    /// holding `pixels` as color table indices in top-to-bottom order.
+    pub fn new(width: u16, height: u16, pixels: Vec<u8>) -> Self {
+        FrameBuilder {
+            left: 0,
+            top: 0,
+            width,
+            height,
+            pixels,
+            local_color_table: None,
+            graphic_control: None,
+        }
+    }
+
+    /// Moves the frame's top left corner to `(left, top)` on the canvas.
+    pub fn position(mut self, left: u16, top: u16) -> Self {
+        self.left = left;
+        self.top = top;
+        self
+    }
+
+    /// Gives the frame a color table of its own.
+    pub fn local_color_table(mut self, ct: ColorTable) -> Self {
+        self.local_color_table = Some(ct);
+        self
+    }
+
+    /// Sets how long the frame is displayed, in hundredths of a second.
+    pub fn delay_centiseconds(mut self, delay: u16) -> Self {
+        self.graphic_control().delay_centiseconds = delay;
+        self
+    }
+
+    /// Sets what happens to the frame once it has been displayed.
+    pub fn disposal_method(mut self, disposal_method: DisposalMethod) -> Self {
+        self.graphic_control().disposal_method = disposal_method;
+        self
+    }
+
+    /// Sets the color table index that is drawn as transparent.
+    pub fn transparent_color_index(mut self, index: u8) -> Self {
+        self.graphic_control().transparent_color_index = Some(index);
+        self
+    }
+
+    fn graphic_control(&mut self) -> &mut GraphicControlExtension {
+        self.graphic_control.get_or_insert(GraphicControlExtension {
+            disposal_method: DisposalMethod::Unspecified(0),
+            user_input: false,
+            transparent_color_index: None,
+            delay_centiseconds: 0,
+        })
+    }
+
+    fn build(self, index: usize) -> Result<Frame, GifError> {
+        if self.pixels.len() != self.width as usize * self.height as usize {
+            return Err(GifError::InvalidFrame {
+                index,
+                message: "pixel data doesn't match the frame's dimensions",
+            });
+        }
+        if let Some(ref table) = self.local_color_table {
+            if table.len() > 256 {
+                return Err(GifError::InvalidColorTable);
+            }
+        }
+
+        Ok(Frame {
+            descriptor: ImageDescriptor {
+                left: self.left,
+                top: self.top,
+                width: self.width,
+                height: self.height,
+                has_local_color_table: self.local_color_table.is_some(),
+                is_interlaced: false,
+                is_sorted: false,
+                local_color_table_size: table_size(self.local_color_table.as_ref()),
+            },
+            local_color_table: self.local_color_table,
+            pixels: self.pixels,
+            graphic_control: self.graphic_control,
+        })
+    }
+}
+
+/// Returns the size in bytes that a descriptor records for `table`, once
+/// padded, or for an empty size field if there is no table.
+fn table_size(table: Option<&ColorTable>) -> u16 {
+    3 * (2 << table.map_or(0, ColorTable::size_field))
+}
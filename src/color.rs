@@ -0,0 +1,338 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Frame;
+
+/// A single RGB entry of a color table.
+#[derive(PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Color {
+    /// Red intensity.
+    pub red: u8,
+    /// Green intensity.
+    pub green: u8,
+    /// Blue intensity.
+    pub blue: u8,
+}
+
+impl Color {
+    /// Black, `#000000`.
+    pub const BLACK: Color = Color::new(0, 0, 0);
+    /// White, `#FFFFFF`.
+    pub const WHITE: Color = Color::new(255, 255, 255);
+    /// Pure red, `#FF0000`.
+    pub const RED: Color = Color::new(255, 0, 0);
+    /// Pure green, `#00FF00`.
+    pub const GREEN: Color = Color::new(0, 255, 0);
+    /// Pure blue, `#0000FF`.
+    pub const BLUE: Color = Color::new(0, 0, 255);
+
+    /// Creates a color from its red, green and blue intensities.
+    pub const fn new(red: u8, green: u8, blue: u8) -> Color {
+        Color { red, green, blue }
+    }
+
+    /// Unpacks a color from `0xRRGGBB`, ignoring the top byte.
+    pub fn from_hex(hex: u32) -> Color {
+        Color::new((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+    }
+
+    /// Returns the channels as `[red, green, blue]`.
+    pub fn to_rgb(self) -> [u8; 3] {
+        [self.red, self.green, self.blue]
+    }
+
+    /// Returns the channels as `[red, green, blue, alpha]`.
+    pub fn to_rgba(self, alpha: u8) -> [u8; 4] {
+        [self.red, self.green, self.blue, alpha]
+    }
+
+    /// Returns the squared Euclidean distance to `other` in RGB space.
+    pub fn distance_sq(&self, other: &Color) -> u32 {
+        let dr = self.red as i32 - other.red as i32;
+        let dg = self.green as i32 - other.green as i32;
+        let db = self.blue as i32 - other.blue as i32;
+        (dr * dr + dg * dg + db * db) as u32
+    }
+
+    /// Returns the luma `0.299 R + 0.587 G + 0.114 B`, scaled by 1000 so it
+    /// is exact.
+    fn luminance(&self) -> u32 {
+        299 * self.red as u32 + 587 * self.green as u32 + 114 * self.blue as u32
+    }
+
+    /// Lays this color over `other` with opacity `alpha`, from 0 for fully
+    /// `other` to 1 for fully this color: each channel becomes
+    /// `alpha * self + (1 - alpha) * other`, rounded and clamped to 0-255.
+    pub fn blend(self, other: Color, alpha: f32) -> Color {
+        let mix = |fg: u8, bg: u8| to_channel(alpha * fg as f32 + (1.0 - alpha) * bg as f32);
+        Color::new(
+            mix(self.red, other.red),
+            mix(self.green, other.green),
+            mix(self.blue, other.blue),
+        )
+    }
+
+    /// Composites the RGBA pixel `fg` over `bg` with the source-over
+    /// operator, using straight (not premultiplied) alpha in both.
+    ///
+    /// Where both are fully transparent the result is transparent black.
+    pub fn blend_rgba(fg: [u8; 4], bg: [u8; 4]) -> [u8; 4] {
+        let fg_alpha = fg[3] as f32 / 255.0;
+        let bg_alpha = bg[3] as f32 / 255.0 * (1.0 - fg_alpha);
+        let alpha = fg_alpha + bg_alpha;
+        if alpha == 0.0 {
+            return TRANSPARENT_BLACK_RGBA;
+        }
+        let mix = |channel: usize| {
+            to_channel((fg[channel] as f32 * fg_alpha + bg[channel] as f32 * bg_alpha) / alpha)
+        };
+        [mix(0), mix(1), mix(2), to_channel(alpha * 255.0)]
+    }
+
+    /// Returns whether this is the color `frame` draws as transparent: the
+    /// entry at its transparent color index in its effective color table,
+    /// which is `global` unless the frame has a local one.
+    ///
+    /// Returns `false` if the frame has no transparent color index, or the
+    /// index lies past the end of the table. Colors are compared by value,
+    /// so a table entry equal to the transparent one also matches.
+    pub fn is_transparent_in(&self, frame: &Frame, global: Option<&ColorTable>) -> bool {
+        let transparent = frame
+            .graphic_control
+            .as_ref()
+            .and_then(|graphic_control| graphic_control.transparent_color_index);
+        match (transparent, frame.effective_color_table(global)) {
+            (Some(index), Some(table)) => table.get(index) == Some(self),
+            _ => false,
+        }
+    }
+
+    /// Returns the color packed as `0xRRGGBB`.
+    pub fn hex_code(&self) -> u32 {
+        ((self.red as u32 & 0xff) << 16) + ((self.green as u32 & 0xff) << 8)
+            + (self.blue as u32 & 0xff)
+    }
+}
+
+/// [`ColorWithAlpha::TRANSPARENT_BLACK`] as a pixel of the RGBA byte buffers
+/// used internally.
+pub(crate) const TRANSPARENT_BLACK_RGBA: [u8; 4] = ColorWithAlpha::TRANSPARENT_BLACK.to_rgba();
+
+/// Rounds a blended channel value to the nearest integer, clamped to 0-255.
+fn to_channel(value: f32) -> u8 {
+    //`as` saturates, so this also clamps, and maps NaN to 0.
+    (value + 0.5) as u8
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((red, green, blue): (u8, u8, u8)) -> Color {
+        Color::new(red, green, blue)
+    }
+}
+
+impl From<Color> for (u8, u8, u8) {
+    fn from(color: Color) -> (u8, u8, u8) {
+        (color.red, color.green, color.blue)
+    }
+}
+
+/// Colors are ordered from darkest to brightest by luma, so the largest
+/// color in a table is its brightest. Colors of equal luma are ordered by
+/// their RGB channels, so that only equal colors compare equal.
+impl Ord for Color {
+    fn cmp(&self, other: &Color) -> Ordering {
+        self.luminance()
+            .cmp(&other.luminance())
+            .then_with(|| self.to_rgb().cmp(&other.to_rgb()))
+    }
+}
+
+impl PartialOrd for Color {
+    fn partial_cmp(&self, other: &Color) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Debug for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Color {{ {:X} }}", self.hex_code())
+    }
+}
+
+/// Formats the color as six lowercase hex digits, `rrggbb`, with a `0x`
+/// prefix under `{:#x}`.
+impl fmt::LowerHex for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        write!(f, "{:06x}", self.hex_code())
+    }
+}
+
+/// Formats the color as six uppercase hex digits, `RRGGBB`, with a `0x`
+/// prefix under `{:#X}`.
+impl fmt::UpperHex for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        write!(f, "{:06X}", self.hex_code())
+    }
+}
+
+//colors serialize as `[red, green, blue]`.
+#[cfg(feature = "serde")]
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_rgb().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let [red, green, blue] = <[u8; 3]>::deserialize(deserializer)?;
+        Ok(Color::new(red, green, blue))
+    }
+}
+
+/// A color with an alpha channel, one pixel of the RGBA buffers returned by
+/// [`Gif::to_rgba_frames`](crate::Gif::to_rgba_frames).
+///
+/// GIF transparency is all or nothing, so decoded pixels are either opaque
+/// or [`ColorWithAlpha::transparent`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ColorWithAlpha {
+    /// Red intensity.
+    pub red: u8,
+    /// Green intensity.
+    pub green: u8,
+    /// Blue intensity.
+    pub blue: u8,
+    /// Opacity, from 0 for fully transparent to 255 for opaque.
+    pub alpha: u8,
+}
+
+impl ColorWithAlpha {
+    /// Fully transparent black, which is what transparent pixels decode to.
+    pub const TRANSPARENT_BLACK: ColorWithAlpha = ColorWithAlpha::new(0, 0, 0, 0);
+
+    /// Creates a color from its red, green, blue and alpha channels.
+    pub const fn new(red: u8, green: u8, blue: u8, alpha: u8) -> ColorWithAlpha {
+        ColorWithAlpha {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Returns [`ColorWithAlpha::TRANSPARENT_BLACK`].
+    pub const fn transparent() -> ColorWithAlpha {
+        ColorWithAlpha::TRANSPARENT_BLACK
+    }
+
+    /// Returns the channels as `[red, green, blue, alpha]`.
+    pub const fn to_rgba(self) -> [u8; 4] {
+        [self.red, self.green, self.blue, self.alpha]
+    }
+}
+
+/// Converts an opaque color, with an alpha of 255.
+impl From<Color> for ColorWithAlpha {
+    fn from(color: Color) -> ColorWithAlpha {
+        ColorWithAlpha::new(color.red, color.green, color.blue, 0xff)
+    }
+}
+
+impl From<[u8; 4]> for ColorWithAlpha {
+    fn from([red, green, blue, alpha]: [u8; 4]) -> ColorWithAlpha {
+        ColorWithAlpha::new(red, green, blue, alpha)
+    }
+}
+
+impl From<ColorWithAlpha> for [u8; 4] {
+    fn from(color: ColorWithAlpha) -> [u8; 4] {
+        color.to_rgba()
+    }
+}
+
+/// A global or local color table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ColorTable(Vec<Color>);
+
+impl ColorTable {
+    /// Creates a table holding `colors`, in order.
+    pub fn new(colors: Vec<Color>) -> ColorTable {
+        ColorTable(colors)
+    }
+
+    /// Returns the color at `index`, or `None` if the table is too short.
+    pub fn get(&self, index: u8) -> Option<&Color> {
+        self.0.get(index as usize)
+    }
+
+    /// Returns the number of colors in the table.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the table has no colors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the index and value of the entry closest to the given color,
+    /// by Euclidean distance in RGB space. Ties go to the lowest index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table is empty.
+    pub fn nearest(&self, r: u8, g: u8, b: u8) -> (u8, &Color) {
+        let target = Color::new(r, g, b);
+        self.0
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, color)| color.distance_sq(&target))
+            .map(|(index, color)| (index as u8, color))
+            .expect("nearest color of an empty color table")
+    }
+
+    /// Returns an iterator over the colors, in table order.
+    pub fn iter(&self) -> impl Iterator<Item = &Color> {
+        self.0.iter()
+    }
+
+    /// Sorts the colors from darkest to brightest by luma, keeping colors of
+    /// equal luma in their current order.
+    ///
+    /// Indices into the table refer to different colors afterwards, so
+    /// pixels using it need remapping.
+    pub fn sort_by_luminance(&mut self) {
+        self.0.sort_by_key(Color::luminance);
+    }
+
+    /// Returns a copy of the table sorted as by
+    /// [`ColorTable::sort_by_luminance`].
+    pub fn sorted(&self) -> ColorTable {
+        let mut table = self.clone();
+        table.sort_by_luminance();
+        table
+    }
+
+    /// Returns the packed size field for the table: it holds `2^(field + 1)`
+    /// colors once padded, up to 256.
+    pub(crate) fn size_field(&self) -> u8 {
+        let mut field = 0;
+        while (2 << field) < self.len() && field < 7 {
+            field += 1;
+        }
+        field
+    }
+}
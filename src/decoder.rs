@@ -0,0 +1,222 @@
+use std::io::BufRead;
+
+use crate::{
+    check_limit, ColorTable, DecodeOptions, Gif, GifError, GifVersion, GraphicControlExtension,
+    ImageDescriptor, LogicalScreenDescriptor, SubBlockReader, COMMENT_LABEL, EXTENSION_INTRODUCER,
+    GRAPHIC_CONTROL_LABEL, IMAGE_SEPARATOR, TRAILER,
+};
+
+/// A piece of a GIF file, as reported by [`GifDecoder`].
+#[derive(Debug)]
+pub enum GifEvent {
+    /// The version from the header. Always the first event.
+    Header(GifVersion),
+    /// The logical screen descriptor.
+    ScreenDescriptor(LogicalScreenDescriptor),
+    /// The global color table, if the file has one.
+    GlobalColorTable(ColorTable),
+    /// A graphic control extension, which applies to the next frame.
+    GraphicControl(GraphicControlExtension),
+    /// The start of a frame.
+    FrameStart(ImageDescriptor),
+    /// The local color table of the current frame, if it has one.
+    LocalColorTable(ColorTable),
+    /// The color table indices of the current frame, in top-to-bottom order.
+    FramePixels(Vec<u8>),
+    /// The end of the current frame.
+    FrameEnd,
+    /// The text of a comment extension.
+    Comment(String),
+    /// The trailer. Always the last event.
+    Done,
+}
+
+/// A pull parser that decodes a GIF one block at a time.
+///
+/// Unlike [`Gif::from_reader`], only the block being decoded is held in
+/// memory. Extensions other than graphic control and comment extensions are
+/// skipped.
+pub struct GifDecoder<R: BufRead> {
+    reader: R,
+    state: State,
+    /// Bytes consumed so far, for error reporting.
+    offset: usize,
+    /// The logical screen descriptor once it has been read, to check each
+    /// image against.
+    lsd: Option<LogicalScreenDescriptor>,
+    options: DecodeOptions,
+    /// Frames started so far, and their pixels, to check against `options`.
+    frame_count: usize,
+    total_pixels: usize,
+}
+
+enum State {
+    Header,
+    ScreenDescriptor,
+    GlobalColorTable(usize),
+    Blocks,
+    LocalColorTable(ImageDescriptor),
+    FramePixels(ImageDescriptor),
+    FrameEnd,
+    Done,
+}
+
+impl<R: BufRead> GifDecoder<R> {
+    /// Creates a decoder that reads from `reader`, with the default
+    /// [`DecodeOptions`].
+    pub fn new(reader: R) -> Self {
+        GifDecoder::with_options(reader, &DecodeOptions::default())
+    }
+
+    /// Creates a decoder that reads from `reader` and fails with
+    /// [`GifError::SizeLimitExceeded`] once the file is larger than `options`
+    /// allow: the canvas as soon as the screen descriptor is read, and each
+    /// frame before its image data is.
+    pub fn with_options(reader: R, options: &DecodeOptions) -> Self {
+        GifDecoder {
+            reader,
+            state: State::Header,
+            offset: 0,
+            lsd: None,
+            options: options.clone(),
+            frame_count: 0,
+            total_pixels: 0,
+        }
+    }
+
+    /// Decodes and returns the next piece of the file, or `None` once
+    /// [`GifEvent::Done`] or an error has been returned.
+    pub fn next_event(&mut self) -> Result<Option<GifEvent>, GifError> {
+        loop {
+            match std::mem::replace(&mut self.state, State::Done) {
+                State::Header => {
+                    let mut buffer = [0; 6];
+                    self.read_exact(&mut buffer)?;
+                    self.state = State::ScreenDescriptor;
+                    return Ok(Some(GifEvent::Header(Gif::parse_version(&buffer)?)));
+                }
+                State::ScreenDescriptor => {
+                    let mut buffer = [0; 7];
+                    self.read_exact(&mut buffer)?;
+                    let lsd = Gif::parse_logical_screen_descriptor(&buffer)?;
+                    self.options.check_canvas(&lsd)?;
+                    self.state = match lsd.has_global_color_table {
+                        true => State::GlobalColorTable(lsd.global_color_table_byte_count()),
+                        _ => State::Blocks,
+                    };
+                    self.lsd = Some(lsd.clone());
+                    return Ok(Some(GifEvent::ScreenDescriptor(lsd)));
+                }
+                State::GlobalColorTable(size) => {
+                    let mut buffer = vec![0; size];
+                    self.read_exact(&mut buffer)?;
+                    self.state = State::Blocks;
+                    return Ok(Some(GifEvent::GlobalColorTable(Gif::parse_color_table(
+                        &buffer,
+                    ))));
+                }
+                State::Blocks => {
+                    let start = self.offset;
+                    let mut introducer = [0];
+                    self.read_exact(&mut introducer)?;
+                    match introducer[0] {
+                        IMAGE_SEPARATOR => {
+                            let mut buffer = [0; 9];
+                            self.read_exact(&mut buffer)?;
+                            let descriptor = Gif::parse_image_descriptor(&buffer);
+                            if let Some(ref lsd) = self.lsd {
+                                descriptor
+                                    .validate_against_lsd(lsd)
+                                    .map_err(|e| e.offset_by(start + 1))?;
+                            }
+                            self.frame_count += 1;
+                            check_limit(self.options.max_frames, self.frame_count)?;
+                            self.total_pixels +=
+                                descriptor.width as usize * descriptor.height as usize;
+                            check_limit(self.options.max_total_pixels, self.total_pixels)?;
+                            let event = GifEvent::FrameStart(descriptor.clone());
+                            self.state = match descriptor.has_local_color_table {
+                                true => State::LocalColorTable(descriptor),
+                                _ => State::FramePixels(descriptor),
+                            };
+                            return Ok(Some(event));
+                        }
+                        EXTENSION_INTRODUCER => {
+                            let mut label = [0];
+                            self.read_exact(&mut label)?;
+                            let data_offset = self.offset;
+                            let sub_blocks = self.read_sub_blocks()?;
+                            self.state = State::Blocks;
+                            match label[0] {
+                                GRAPHIC_CONTROL_LABEL => {
+                                    let data = SubBlockReader(&sub_blocks).data();
+                                    let extension = Gif::parse_graphic_control_extension(&data)
+                                        .map_err(|e| e.offset_by(data_offset))?;
+                                    return Ok(Some(GifEvent::GraphicControl(extension)));
+                                }
+                                COMMENT_LABEL => {
+                                    let data = SubBlockReader(&sub_blocks).data();
+                                    let comment = String::from_utf8_lossy(&data).into_owned();
+                                    return Ok(Some(GifEvent::Comment(comment)));
+                                }
+                                _ => {}
+                            }
+                        }
+                        TRAILER => return Ok(Some(GifEvent::Done)),
+                        _ => {
+                            return Err(GifError::MalformedBlock {
+                                offset: start,
+                                message: "unknown block introducer",
+                            })
+                        }
+                    }
+                }
+                State::LocalColorTable(descriptor) => {
+                    let mut buffer = vec![0; descriptor.local_color_table_size as usize];
+                    self.read_exact(&mut buffer)?;
+                    self.state = State::FramePixels(descriptor);
+                    return Ok(Some(GifEvent::LocalColorTable(Gif::parse_color_table(
+                        &buffer,
+                    ))));
+                }
+                State::FramePixels(descriptor) => {
+                    let mut min_code_size = [0];
+                    self.read_exact(&mut min_code_size)?;
+                    let data_offset = self.offset;
+                    let sub_blocks = self.read_sub_blocks()?;
+                    let pixels = Gif::decode_image_data(&descriptor, min_code_size[0], &sub_blocks)
+                        .map_err(|e| e.offset_by(data_offset))?;
+                    self.state = State::FrameEnd;
+                    return Ok(Some(GifEvent::FramePixels(pixels)));
+                }
+                State::FrameEnd => {
+                    self.state = State::Blocks;
+                    return Ok(Some(GifEvent::FrameEnd));
+                }
+                State::Done => return Ok(None),
+            }
+        }
+    }
+
+    fn read_exact(&mut self, buffer: &mut [u8]) -> Result<(), GifError> {
+        Gif::read_exact(&mut self.reader, buffer)?;
+        self.offset += buffer.len();
+        Ok(())
+    }
+
+    /// Reads a run of sub-blocks, keeping the length bytes but not the terminator.
+    fn read_sub_blocks(&mut self) -> Result<Vec<u8>, GifError> {
+        let mut sub_blocks = vec![];
+        loop {
+            let mut len = [0];
+            self.read_exact(&mut len)?;
+            if len[0] == 0 {
+                return Ok(sub_blocks);
+            }
+            let start = sub_blocks.len();
+            sub_blocks.resize(start + 1 + len[0] as usize, 0);
+            sub_blocks[start] = len[0];
+            self.read_exact(&mut sub_blocks[start + 1..])?;
+        }
+    }
+}
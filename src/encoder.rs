@@ -0,0 +1,238 @@
+use std::io::{self, Write};
+
+use crate::sub_block::write_sub_blocks;
+use crate::{
+    lzw, ColorTable, DisposalMethod, Frame, GifError, PlainTextExtension, APPLICATION_LABEL,
+    COMMENT_LABEL, EXTENSION_INTRODUCER, GRAPHIC_CONTROL_LABEL, IMAGE_SEPARATOR, PLAIN_TEXT_LABEL,
+    TRAILER,
+};
+
+/// Writes frames to a GIF89a file.
+///
+/// Nothing is written until the first frame or [`GifEncoder::finish`]; the
+/// file is only complete once `finish` has been called.
+pub struct GifEncoder<W: Write> {
+    writer: W,
+    width: u16,
+    height: u16,
+    global_color_table: Option<ColorTable>,
+    //logical screen descriptor fields that `Gif::save` carries over.
+    pub(crate) color_resolution: u8,
+    pub(crate) background_color_index: u8,
+    pub(crate) pixel_aspect_ratio: u8,
+    /// The number of frames written so far, for error reporting.
+    frame_count: usize,
+    header_written: bool,
+}
+
+impl<W: Write> GifEncoder<W> {
+    /// Creates an encoder for a canvas of `width` by `height` pixels.
+    ///
+    /// Color tables are padded with black to the next power of two; at most
+    /// 256 colors are written.
+    pub fn new(
+        writer: W,
+        width: u16,
+        height: u16,
+        global_color_table: Option<&ColorTable>,
+    ) -> Self {
+        GifEncoder {
+            writer,
+            width,
+            height,
+            global_color_table: global_color_table.cloned(),
+            color_resolution: 8,
+            background_color_index: 0,
+            pixel_aspect_ratio: 0,
+            frame_count: 0,
+            header_written: false,
+        }
+    }
+
+    /// Writes `frame`, preceded by its graphic control extension if it has one.
+    ///
+    /// The image is always written non-interlaced. Fails if the frame's pixel
+    /// data doesn't match its dimensions.
+    pub fn write_frame(&mut self, frame: &Frame) -> Result<(), GifError> {
+        let descriptor = &frame.descriptor;
+        if frame.pixels.len() != descriptor.width as usize * descriptor.height as usize {
+            return Err(GifError::InvalidFrame {
+                index: self.frame_count,
+                message: "pixel data doesn't match the frame's dimensions",
+            });
+        }
+        self.write_header()?;
+
+        let mut bytes = vec![];
+        if let Some(ref graphic_control) = frame.graphic_control {
+            let disposal_method = match graphic_control.disposal_method {
+                DisposalMethod::DoNotDispose => 1,
+                DisposalMethod::RestoreToBackground => 2,
+                DisposalMethod::RestoreToPrevious => 3,
+                DisposalMethod::Unspecified(value) => value & 0b111,
+            };
+            let mut packed_fields = disposal_method << 2;
+            if graphic_control.user_input {
+                packed_fields |= 0b00000010;
+            }
+            if graphic_control.transparent_color_index.is_some() {
+                packed_fields |= 0b00000001;
+            }
+            bytes.extend_from_slice(&[EXTENSION_INTRODUCER, GRAPHIC_CONTROL_LABEL, 4]);
+            bytes.push(packed_fields);
+            push_u16(&mut bytes, graphic_control.delay_centiseconds);
+            bytes.push(graphic_control.transparent_color_index.unwrap_or(0));
+            bytes.push(0);
+        }
+
+        bytes.push(IMAGE_SEPARATOR);
+        push_u16(&mut bytes, descriptor.left);
+        push_u16(&mut bytes, descriptor.top);
+        push_u16(&mut bytes, descriptor.width);
+        push_u16(&mut bytes, descriptor.height);
+        match frame.local_color_table {
+            Some(ref table) => {
+                bytes.push(0b10000000 | table.size_field());
+                push_color_table(&mut bytes, table);
+            }
+            None => bytes.push(0),
+        }
+
+        let table_len = frame
+            .effective_color_table(self.global_color_table.as_ref())
+            .map_or(0, |table| table.len().min(256));
+        let min_code_size = lzw::min_code_size(table_len, &frame.pixels);
+        bytes.push(min_code_size);
+        bytes.extend_from_slice(&lzw::encode(min_code_size, &frame.pixels));
+
+        self.frame_count += 1;
+        self.writer.write_all(&bytes).map_err(GifError::Io)
+    }
+
+    /// Writes a NETSCAPE2.0 extension making the animation repeat
+    /// `loop_count` times, or forever if it is `0`.
+    ///
+    /// Like any extension, it only applies to what follows it, so it should be
+    /// written before the first frame.
+    pub fn write_loop_count(&mut self, loop_count: u16) -> Result<(), GifError> {
+        let mut data = b"NETSCAPE2.0".to_vec();
+        data.push(1);
+        push_u16(&mut data, loop_count);
+        self.write_extension(APPLICATION_LABEL, &data)
+    }
+
+    /// Writes a comment extension.
+    pub fn write_comment(&mut self, comment: &str) -> Result<(), GifError> {
+        self.write_extension(COMMENT_LABEL, comment.as_bytes())
+    }
+
+    /// Writes a plain text extension.
+    pub fn write_plain_text(&mut self, plain_text: &PlainTextExtension) -> Result<(), GifError> {
+        let mut header = vec![];
+        push_u16(&mut header, plain_text.text_grid_left);
+        push_u16(&mut header, plain_text.text_grid_top);
+        push_u16(&mut header, plain_text.text_grid_width);
+        push_u16(&mut header, plain_text.text_grid_height);
+        header.extend_from_slice(&[
+            plain_text.cell_width,
+            plain_text.cell_height,
+            plain_text.foreground_color_index,
+            plain_text.background_color_index,
+        ]);
+        self.write_header()?;
+        let mut bytes = vec![EXTENSION_INTRODUCER, PLAIN_TEXT_LABEL, header.len() as u8];
+        bytes.extend_from_slice(&header);
+        write_sub_blocks(plain_text.text_data.as_bytes(), &mut bytes);
+        self.writer.write_all(&bytes).map_err(GifError::Io)
+    }
+
+    /// Writes an extension with the given label and data, split into
+    /// sub-blocks.
+    ///
+    /// For application extensions, the first 11 bytes of `data` are the
+    /// identifier and authentication code, and get a sub-block of their own.
+    pub fn write_extension(&mut self, label: u8, data: &[u8]) -> Result<(), GifError> {
+        self.write_header()?;
+        let mut bytes = vec![EXTENSION_INTRODUCER, label];
+        let mut data = data;
+        if label == APPLICATION_LABEL && data.len() >= 11 {
+            bytes.push(11);
+            bytes.extend_from_slice(&data[..11]);
+            data = &data[11..];
+        }
+        write_sub_blocks(data, &mut bytes);
+        self.writer.write_all(&bytes).map_err(GifError::Io)
+    }
+
+    /// Writes the trailer and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, GifError> {
+        self.write_header()?;
+        self.writer.write_all(&[TRAILER]).map_err(GifError::Io)?;
+        self.writer.flush().map_err(GifError::Io)?;
+        Ok(self.writer)
+    }
+
+    /// Writes the header, logical screen descriptor and global color table,
+    /// unless they have been written already.
+    fn write_header(&mut self) -> Result<(), GifError> {
+        if self.header_written {
+            return Ok(());
+        }
+
+        let mut bytes = b"GIF89a".to_vec();
+        push_u16(&mut bytes, self.width);
+        push_u16(&mut bytes, self.height);
+        let packed_fields = (self.color_resolution.wrapping_sub(1) & 0b111) << 4;
+        match self.global_color_table {
+            Some(ref table) => bytes.push(0b10000000 | packed_fields | table.size_field()),
+            None => bytes.push(packed_fields),
+        }
+        bytes.extend_from_slice(&[self.background_color_index, self.pixel_aspect_ratio]);
+        if let Some(ref table) = self.global_color_table {
+            push_color_table(&mut bytes, table);
+        }
+
+        self.writer.write_all(&bytes).map_err(GifError::Io)?;
+        self.header_written = true;
+        Ok(())
+    }
+}
+
+/// Passes writes through to a writer, counting the bytes that were written.
+pub(crate) struct CountingWriter<W> {
+    writer: W,
+    pub(crate) count: usize,
+}
+
+impl<W: Write> CountingWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        CountingWriter { writer, count: 0 }
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.count += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+fn push_u16(bytes: &mut Vec<u8>, value: u16) {
+    bytes.push(value as u8);
+    bytes.push((value >> 8) as u8);
+}
+
+fn push_color_table(bytes: &mut Vec<u8>, table: &ColorTable) {
+    let len = 2 << table.size_field();
+    for color in table.iter().take(len) {
+        bytes.extend_from_slice(&color.to_rgb());
+    }
+    for _ in table.len()..len {
+        bytes.extend_from_slice(&[0, 0, 0]);
+    }
+}
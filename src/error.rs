@@ -0,0 +1,120 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::io;
+
+use alloc::string::String;
+
+/// Errors that can occur while decoding a GIF.
+///
+/// New variants may be added in minor releases as the decoder learns to
+/// diagnose more problems, so matches outside this crate need a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GifError {
+    /// Reading from the underlying source failed.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    /// The data is not a GIF file, or lacks what is needed to render it.
+    InvalidGifFile,
+    /// The header carries a version other than `87a` or `89a`.
+    UnsupportedVersion(String),
+    /// The data ended before the structure being read was complete.
+    UnexpectedEof,
+    /// A block's contents are inconsistent with its structure.
+    MalformedBlock {
+        /// Byte offset of the problem from the start of the file.
+        offset: usize,
+        /// What is wrong with the block.
+        message: &'static str,
+    },
+    /// A frame is inconsistent with the rest of the file.
+    InvalidFrame {
+        /// Index of the offending frame.
+        index: usize,
+        /// What is wrong with the frame.
+        message: &'static str,
+    },
+    /// A frame index or canvas coordinate lies outside the GIF.
+    OutOfBounds,
+    /// A color table can't be used where it was given, such as one with more
+    /// than 256 colors or a global color table larger than the one it
+    /// replaces.
+    InvalidColorTable,
+    /// A width or height is zero where an image needs at least one pixel.
+    InvalidDimensions,
+    /// Two images that must be the same size are not.
+    DimensionMismatch {
+        /// The size required, as `(width, height)`.
+        expected: (u16, u16),
+        /// The size found, as `(width, height)`.
+        actual: (u16, u16),
+    },
+    /// The GIF is larger than the decode options allow.
+    SizeLimitExceeded {
+        /// The largest value allowed.
+        limit: usize,
+        /// The value found in the file.
+        actual: usize,
+    },
+}
+
+impl GifError {
+    /// Moves the offset of a `MalformedBlock` forward by `base`, for errors
+    /// raised while parsing a slice that starts `base` bytes into the file.
+    pub(crate) fn offset_by(self, base: usize) -> GifError {
+        match self {
+            GifError::MalformedBlock { offset, message } => GifError::MalformedBlock {
+                offset: base + offset,
+                message,
+            },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for GifError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            #[cfg(feature = "std")]
+            GifError::Io(ref e) => write!(f, "I/O error: {}", e),
+            GifError::InvalidGifFile => write!(f, "invalid GIF file"),
+            GifError::UnsupportedVersion(ref version) => {
+                write!(f, "unsupported GIF version: {}", version)
+            }
+            GifError::UnexpectedEof => write!(f, "unexpected end of GIF data"),
+            GifError::MalformedBlock { offset, message } => {
+                write!(f, "malformed block at byte {}: {}", offset, message)
+            }
+            GifError::InvalidFrame { index, message } => {
+                write!(f, "invalid frame {}: {}", index, message)
+            }
+            GifError::OutOfBounds => write!(f, "frame index or coordinate out of bounds"),
+            GifError::InvalidColorTable => write!(f, "invalid color table"),
+            GifError::InvalidDimensions => write!(f, "invalid dimensions"),
+            GifError::DimensionMismatch { expected, actual } => write!(
+                f,
+                "dimension mismatch: expected {}x{}, found {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            GifError::SizeLimitExceeded { limit, actual } => {
+                write!(
+                    f,
+                    "size limit exceeded: {} is over the limit of {}",
+                    actual, limit
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for GifError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            GifError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::string::String;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rendering parameters for the image that follows it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GraphicControlExtension {
+    /// What to do with the image once it has been displayed.
+    pub disposal_method: DisposalMethod,
+    /// Whether the viewer should wait for user input before continuing.
+    pub user_input: bool,
+    /// Index of the color to treat as transparent, if any.
+    pub transparent_color_index: Option<u8>,
+    /// How long to display the image, in hundredths of a second.
+    pub delay_centiseconds: u16,
+}
+
+/// How an image is treated after it has been displayed.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DisposalMethod {
+    /// Leave the image in place.
+    DoNotDispose,
+    /// Restore the image's area to the background color.
+    RestoreToBackground,
+    /// Restore the image's area to what was there before it was drawn.
+    RestoreToPrevious,
+    /// No disposal specified (0), or a value reserved by the spec (4-7).
+    Unspecified(u8),
+}
+
+/// The NETSCAPE2.0 application extension, which controls animation looping.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetscapeExtension {
+    /// How many times the animation repeats; `0` means forever.
+    pub loop_count: u16,
+}
+
+/// Text to be rendered over the canvas on a grid of character cells.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlainTextExtension {
+    /// Column of the grid's left edge on the canvas.
+    pub text_grid_left: u16,
+    /// Row of the grid's top edge on the canvas.
+    pub text_grid_top: u16,
+    /// Grid width in pixels.
+    pub text_grid_width: u16,
+    /// Grid height in pixels.
+    pub text_grid_height: u16,
+    /// Width of each character cell in pixels.
+    pub cell_width: u8,
+    /// Height of each character cell in pixels.
+    pub cell_height: u8,
+    /// Global color table index of the text color.
+    pub foreground_color_index: u8,
+    /// Global color table index of the grid's background color.
+    pub background_color_index: u8,
+    /// The text to render.
+    pub text_data: String,
+}
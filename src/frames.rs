@@ -0,0 +1,42 @@
+use core::iter::FusedIterator;
+use core::slice;
+
+use crate::Frame;
+
+/// An iterator over the frames of a GIF in stream order, from either end;
+/// see [`Gif::iter_frames`](crate::Gif::iter_frames).
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct FrameIter<'a> {
+    frames: slice::Iter<'a, Frame>,
+}
+
+impl<'a> FrameIter<'a> {
+    pub(crate) fn new(frames: &'a [Frame]) -> FrameIter<'a> {
+        FrameIter {
+            frames: frames.iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for FrameIter<'a> {
+    type Item = &'a Frame;
+
+    fn next(&mut self) -> Option<&'a Frame> {
+        self.frames.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.frames.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for FrameIter<'a> {
+    fn next_back(&mut self) -> Option<&'a Frame> {
+        self.frames.next_back()
+    }
+}
+
+impl<'a> ExactSizeIterator for FrameIter<'a> {}
+
+impl<'a> FusedIterator for FrameIter<'a> {}
@@ -0,0 +1,191 @@
+use core::fmt;
+use std::io::prelude::*;
+
+use crate::{
+    Gif, GifError, SubBlockReader, APPLICATION_LABEL, COMMENT_LABEL, EXTENSION_INTRODUCER,
+    GRAPHIC_CONTROL_LABEL, IMAGE_SEPARATOR, PLAIN_TEXT_LABEL, TRAILER,
+};
+
+/// Prints the block structure of a GIF without decoding its images.
+pub struct GifInspector;
+
+impl GifInspector {
+    /// Reads a GIF from `reader` and prints a tree of its blocks to `writer`:
+    /// the header, the logical screen descriptor, and each extension and
+    /// image with its byte offset, fields and sub-block sizes.
+    ///
+    /// A block that can't be read ends the listing with an error, after
+    /// everything before it has been printed.
+    pub fn inspect<R: Read, W: Write>(reader: &mut R, writer: &mut W) -> Result<(), GifError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).map_err(GifError::Io)?;
+
+        let mut header = [0; 6];
+        header.copy_from_slice(Gif::take(&bytes, 0, 6)?);
+        let version = Gif::parse_version(&header)?;
+        line(writer, format_args!("Header @0: {}", version))?;
+
+        let mut descriptor = [0; 7];
+        descriptor.copy_from_slice(Gif::take(&bytes, 6, 7)?);
+        let lsd = Gif::parse_logical_screen_descriptor(&descriptor)?;
+        line(
+            writer,
+            format_args!("Logical Screen Descriptor @6: {}x{}", lsd.width, lsd.height),
+        )?;
+        line(
+            writer,
+            format_args!(
+                "  global color table: {}, sorted: {}, color resolution: {} bits",
+                lsd.has_global_color_table, lsd.is_global_color_table_sorted, lsd.color_resolution
+            ),
+        )?;
+        line(
+            writer,
+            format_args!(
+                "  background color index: {}, pixel aspect ratio: {}",
+                descriptor[5], lsd.pixel_aspect_ratio_raw
+            ),
+        )?;
+
+        let mut i = 13;
+        if lsd.has_global_color_table {
+            let size = lsd.global_color_table_byte_count();
+            Gif::take(&bytes, i, size)?;
+            line(
+                writer,
+                format_args!("Global Color Table @{}: {} colors", i, size / 3),
+            )?;
+            i += size;
+        }
+
+        loop {
+            match bytes.get(i) {
+                Some(&IMAGE_SEPARATOR) => i = image(&bytes, i, writer)?,
+                Some(&EXTENSION_INTRODUCER) => i = extension(&bytes, i, writer)?,
+                Some(&TRAILER) => return line(writer, format_args!("Trailer @{}", i)),
+                Some(_) => {
+                    return Err(GifError::MalformedBlock {
+                        offset: i,
+                        message: "unknown block introducer",
+                    })
+                }
+                None => return Err(GifError::UnexpectedEof),
+            }
+        }
+    }
+}
+
+/// Prints the image starting at `start`, returning the offset of the next block.
+fn image<W: Write>(bytes: &[u8], start: usize, writer: &mut W) -> Result<usize, GifError> {
+    let descriptor = Gif::parse_image_descriptor(Gif::take(bytes, start + 1, 9)?);
+    line(
+        writer,
+        format_args!(
+            "Image Descriptor @{}: {}x{} at ({}, {})",
+            start, descriptor.width, descriptor.height, descriptor.left, descriptor.top
+        ),
+    )?;
+    line(
+        writer,
+        format_args!(
+            "  local color table: {}, interlaced: {}, sorted: {}",
+            descriptor.has_local_color_table, descriptor.is_interlaced, descriptor.is_sorted
+        ),
+    )?;
+
+    let mut i = start + 10;
+    if descriptor.has_local_color_table {
+        let size = descriptor.local_color_table_size as usize;
+        Gif::take(bytes, i, size)?;
+        line(
+            writer,
+            format_args!("  Local Color Table @{}: {} colors", i, size / 3),
+        )?;
+        i += size;
+    }
+
+    let min_code_size = *bytes.get(i).ok_or(GifError::UnexpectedEof)?;
+    line(
+        writer,
+        format_args!("  LZW minimum code size: {}", min_code_size),
+    )?;
+    let (blocks, len) = SubBlockReader::split(&bytes[i + 1..]).map_err(|e| e.offset_by(i + 1))?;
+    line(
+        writer,
+        format_args!("  Image Data @{}: {}", i + 1, Summary(blocks)),
+    )?;
+    Ok(i + 1 + len)
+}
+
+/// Prints the extension starting at `start`, returning the offset of the next
+/// block.
+fn extension<W: Write>(bytes: &[u8], start: usize, writer: &mut W) -> Result<usize, GifError> {
+    let label = *bytes.get(start + 1).ok_or(GifError::UnexpectedEof)?;
+    let (blocks, len) =
+        SubBlockReader::split(&bytes[start + 2..]).map_err(|e| e.offset_by(start + 2))?;
+    let name = match label {
+        GRAPHIC_CONTROL_LABEL => "Graphic Control Extension",
+        APPLICATION_LABEL => "Application Extension",
+        COMMENT_LABEL => "Comment Extension",
+        PLAIN_TEXT_LABEL => "Plain Text Extension",
+        _ => "Unknown Extension",
+    };
+    line(
+        writer,
+        format_args!(
+            "{} 0x{:02X} @{}: {}",
+            name,
+            label,
+            start,
+            Summary(blocks.clone())
+        ),
+    )?;
+
+    match (label, blocks.clone().next()) {
+        (GRAPHIC_CONTROL_LABEL, Some(block)) => {
+            let graphic_control =
+                Gif::parse_graphic_control_extension(block).map_err(|e| e.offset_by(start + 3))?;
+            line(
+                writer,
+                format_args!(
+                    "  disposal method: {:?}, user input: {}, transparent color index: {:?}, delay: {} cs",
+                    graphic_control.disposal_method,
+                    graphic_control.user_input,
+                    graphic_control.transparent_color_index,
+                    graphic_control.delay_centiseconds
+                ),
+            )?;
+        }
+        (APPLICATION_LABEL, Some(block)) => {
+            line(
+                writer,
+                format_args!("  identifier: {}", String::from_utf8_lossy(block)),
+            )?;
+        }
+        _ => {}
+    }
+    Ok(start + 2 + len)
+}
+
+fn line<W: Write>(writer: &mut W, args: fmt::Arguments) -> Result<(), GifError> {
+    writeln!(writer, "{}", args).map_err(GifError::Io)
+}
+
+/// Formats a run of sub-blocks as its block count and data length.
+struct Summary<'a>(SubBlockReader<'a>);
+
+impl<'a> fmt::Display for Summary<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (count, len) = self
+            .0
+            .clone()
+            .fold((0, 0), |(count, len), block| (count + 1, len + block.len()));
+        write!(
+            f,
+            "{} sub-block{}, {} bytes",
+            count,
+            if count == 1 { "" } else { "s" },
+            len
+        )
+    }
+}
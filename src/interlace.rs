@@ -0,0 +1,22 @@
+use alloc::vec::Vec;
+
+/// The starting row and row step of each of the four interlace passes.
+const PASSES: [(usize, usize); 4] = [(0, 8), (4, 8), (2, 4), (1, 2)];
+
+/// Reorders the rows of an interlaced image into top-to-bottom order.
+pub fn deinterlace(pixels: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let mut output = vec![0; width * height];
+    if width == 0 {
+        return output;
+    }
+    let mut rows = pixels.chunks(width);
+    for &(start, step) in PASSES.iter() {
+        for y in (start..height).step_by(step) {
+            match rows.next() {
+                Some(row) => output[y * width..y * width + row.len()].copy_from_slice(row),
+                None => return output,
+            }
+        }
+    }
+    output
+}
@@ -0,0 +1,2149 @@
+//! A decoder and encoder for the GIF87a and GIF89a image formats.
+//!
+//! See the [GIF89a specification](https://www.w3.org/Graphics/GIF/spec-gif89a.txt).
+//!
+//! # Thread safety
+//!
+//! Every type in the crate is `Send` and `Sync`, except that `GifDecoder`
+//! and `GifEncoder` are only as thread-safe as the reader or writer they
+//! wrap. A GIF can be decoded on a background thread and then shared, for
+//! example in an `Arc<Gif>`.
+//!
+//! # Serialization
+//!
+//! With the `serde` feature, the decoded types implement `Serialize` and
+//! `Deserialize`. Colors are written as `[red, green, blue]` arrays and
+//! pixel data as base64 strings:
+//!
+//! ```
+//! # #[cfg(feature = "serde")]
+//! # {
+//! let gif = gif::Gif::from_bytes(include_bytes!("../tests/fixtures/sample.gif")).unwrap();
+//! let json = serde_json::to_string(&gif).unwrap();
+//! assert!(json.contains(r#""global_color_table":[[0,0,0],[255,255,255],[255,0,0],[0,0,0]]"#));
+//! assert!(json.contains(r#""pixels":"AAECAAECAAE=""#));
+//!
+//! let decoded: gif::Gif = serde_json::from_str(&json).unwrap();
+//! assert_eq!(decoded.frames[0].pixels, gif.frames[0].pixels);
+//! assert_eq!(decoded.global_color_table, gif.global_color_table);
+//! # }
+//! ```
+
+#![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Bound, Range, RangeBounds};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "async-tokio")]
+use std::future::Future;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::io::prelude::*;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+mod animator;
+#[cfg(feature = "apng")]
+mod apng;
+#[cfg(feature = "serde")]
+mod base64;
+mod builder;
+mod color;
+#[cfg(feature = "std")]
+mod decoder;
+#[cfg(feature = "std")]
+mod encoder;
+mod error;
+mod extension;
+mod frames;
+#[cfg(feature = "std")]
+mod inspector;
+mod interlace;
+pub mod lzw;
+mod optimizer;
+mod palette;
+mod parser;
+pub mod quantize;
+mod sub_block;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use animator::{Animator, GifSeeker, IntoIter};
+pub use builder::{FrameBuilder, GifBuilder};
+pub use color::{Color, ColorTable, ColorWithAlpha};
+#[cfg(feature = "std")]
+pub use decoder::{GifDecoder, GifEvent};
+#[cfg(feature = "std")]
+use encoder::CountingWriter;
+#[cfg(feature = "std")]
+pub use encoder::GifEncoder;
+pub use error::GifError;
+pub use extension::{
+    DisposalMethod, GraphicControlExtension, NetscapeExtension, PlainTextExtension,
+};
+pub use frames::FrameIter;
+#[cfg(feature = "std")]
+pub use inspector::GifInspector;
+pub use optimizer::GifOptimizer;
+pub use palette::Palette;
+#[cfg(feature = "std")]
+pub use parser::GifParser;
+pub use parser::{Block, ExtensionBlock, ImageBlock};
+pub use sub_block::SubBlockReader;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmGif;
+
+const IMAGE_SEPARATOR: u8 = 0x2C;
+const EXTENSION_INTRODUCER: u8 = 0x21;
+const TRAILER: u8 = 0x3B;
+
+const GRAPHIC_CONTROL_LABEL: u8 = 0xF9;
+const APPLICATION_LABEL: u8 = 0xFF;
+const COMMENT_LABEL: u8 = 0xFE;
+const PLAIN_TEXT_LABEL: u8 = 0x01;
+
+/// The shortest frame delay browsers honour; shorter delays are raised to it.
+const MIN_FRAME_DELAY_MS: f64 = 20.0;
+
+/// A decoded GIF file.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Gif {
+    /// The version declared in the header.
+    pub version: GifVersion,
+    /// The logical screen descriptor.
+    pub lsd: LogicalScreenDescriptor,
+    /// The global color table, if the file has one.
+    pub global_color_table: Option<ColorTable>,
+    /// The descriptors of every image in the file, in stream order: the same
+    /// as each frame's `descriptor`.
+    ///
+    /// Methods that add, remove or rebuild frames keep this in step. Code
+    /// that edits `frames` directly should update it too; the encoder only
+    /// reads `frames`.
+    pub image_descriptors: Vec<ImageDescriptor>,
+    /// The images in the file, in stream order.
+    pub frames: Vec<Frame>,
+    /// How many times the animation repeats, from the NETSCAPE2.0 extension.
+    ///
+    /// `Some(0)` means the animation loops forever.
+    pub loop_count: Option<u16>,
+    /// The text of every comment extension, in stream order.
+    pub comments: Vec<String>,
+    /// Every plain text extension, in stream order.
+    pub plain_text_extensions: Vec<PlainTextExtension>,
+    /// The label and concatenated data of every extension the decoder doesn't
+    /// interpret, including application extensions other than NETSCAPE2.0.
+    pub unknown_extensions: Vec<(u8, Vec<u8>)>,
+}
+
+/// The canvas description that follows the header.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LogicalScreenDescriptor {
+    /// Canvas width in pixels.
+    pub width: u16,
+    /// Canvas height in pixels.
+    pub height: u16,
+    /// Whether a global color table follows the descriptor.
+    pub has_global_color_table: bool,
+    /// Bits per primary color available to the original image, from 1 to 8;
+    /// see `color_count`.
+    pub color_resolution: u8,
+    /// Whether the global color table is sorted by decreasing importance.
+    pub is_global_color_table_sorted: bool,
+    /// Index of the background color in the global color table.
+    pub background_color_index: Option<u8>,
+    /// The 3-bit size field of the global color table, as stored in the
+    /// file: the table holds `2^(field + 1)` colors. See
+    /// `actual_global_color_table_size` and `global_color_table_byte_count`.
+    pub global_color_table_size_flag: u8,
+    /// Raw pixel aspect ratio byte; see `pixel_aspect_ratio_f64` for its meaning.
+    pub pixel_aspect_ratio_raw: u8,
+}
+
+/// A single image of the file together with the data needed to render it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Frame {
+    /// Where the image sits on the canvas.
+    pub descriptor: ImageDescriptor,
+    /// The color table that applies to this image only, if it has one.
+    pub local_color_table: Option<ColorTable>,
+    /// Color table indices of the image, one byte per pixel, row by row.
+    ///
+    /// There is exactly one index for each of the `width * height` pixels
+    /// of the descriptor. Decoding pads image data that ends early with
+    /// index 0. Frames built by hand must have the right length too, or
+    /// they fail [`Gif::validate`] and can't be encoded.
+    #[cfg_attr(feature = "serde", serde(with = "base64"))]
+    pub pixels: Vec<u8>,
+    /// The graphic control extension that preceded the image, if any.
+    pub graphic_control: Option<GraphicControlExtension>,
+}
+
+/// The position and layout of a single image within the canvas.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ImageDescriptor {
+    /// Column of the image's left edge on the canvas.
+    pub left: u16,
+    /// Row of the image's top edge on the canvas.
+    pub top: u16,
+    /// Image width in pixels.
+    pub width: u16,
+    /// Image height in pixels.
+    pub height: u16,
+    /// Whether a local color table follows the descriptor.
+    pub has_local_color_table: bool,
+    /// Whether the image rows are stored in interlaced order.
+    pub is_interlaced: bool,
+    /// Whether the local color table is sorted by decreasing importance.
+    pub is_sorted: bool,
+    /// Size of the local color table in bytes.
+    pub local_color_table_size: u16,
+}
+
+/// How two composited frames differ; see [`Gif::diff_frames`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffResult {
+    /// The number of canvas pixels whose RGBA values differ.
+    pub changed_pixels: usize,
+    /// The smallest rectangle holding every changed pixel, as
+    /// `(left, top, width, height)`. All zero when nothing changed.
+    pub bounding_box: (u16, u16, u16, u16),
+}
+
+/// Summary figures for a GIF; see [`Gif::stats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GifStats {
+    /// The number of images in the file.
+    pub frame_count: usize,
+    /// Canvas width in pixels.
+    pub canvas_width: u16,
+    /// Canvas height in pixels.
+    pub canvas_height: u16,
+    /// The number of pixels in all the frames together.
+    pub total_pixels: u64,
+    /// The number of distinct colors in the global and local color tables,
+    /// whether or not any pixel uses them.
+    pub unique_palette_entries: usize,
+    /// Whether any frame has a transparent color index.
+    pub has_transparency: bool,
+    /// How long the animation plays for, as [`Gif::total_duration_ms`]
+    /// gives it.
+    pub total_duration_ms: f64,
+    /// The size of the frames' compressed image data as this crate encodes
+    /// it, including the minimum code size bytes and sub-block lengths.
+    pub compressed_size_bytes: usize,
+}
+
+/// Limits on what the decoder will allocate for a GIF, to stop huge or
+/// hostile files from exhausting memory. [`Gif`] and [`GifDecoder`] both
+/// check them before decompressing each image, and never decompress more
+/// pixels than the image declares.
+///
+/// The defaults allow canvases up to 16384 pixels on a side, 10,000 frames
+/// and 256 megapixels of image data.
+#[derive(Debug, Clone)]
+pub struct DecodeOptions {
+    /// The widest canvas to accept.
+    pub max_width: u16,
+    /// The tallest canvas to accept.
+    pub max_height: u16,
+    /// The most frames to accept.
+    pub max_frames: usize,
+    /// The most pixels to accept, both for the canvas and summed across
+    /// every frame.
+    pub max_total_pixels: usize,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> DecodeOptions {
+        DecodeOptions {
+            max_width: 16384,
+            max_height: 16384,
+            max_frames: 10_000,
+            max_total_pixels: 1 << 28,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// Checks the canvas size declared by `lsd`.
+    fn check_canvas(&self, lsd: &LogicalScreenDescriptor) -> Result<(), GifError> {
+        check_limit(self.max_width as usize, lsd.width as usize)?;
+        check_limit(self.max_height as usize, lsd.height as usize)?;
+        check_limit(
+            self.max_total_pixels,
+            lsd.width as usize * lsd.height as usize,
+        )
+    }
+}
+
+/// The GIF versions understood by the decoder.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GifVersion {
+    /// GIF87a.
+    ///
+    /// The 87a specification already defines the extension block format and
+    /// asks decoders to skip extensions they don't know, so extensions in
+    /// these files are read just as in 89a files.
+    V87a,
+    /// GIF89a.
+    V89a,
+}
+
+/// A 1 by 1 canvas with no global color table, a color resolution of 1 bit
+/// and no aspect ratio.
+impl Default for LogicalScreenDescriptor {
+    fn default() -> LogicalScreenDescriptor {
+        LogicalScreenDescriptor {
+            width: 1,
+            height: 1,
+            has_global_color_table: false,
+            color_resolution: 1,
+            is_global_color_table_sorted: false,
+            background_color_index: None,
+            global_color_table_size_flag: 0,
+            pixel_aspect_ratio_raw: 0,
+        }
+    }
+}
+
+impl LogicalScreenDescriptor {
+    /// Returns the pixel width divided by the pixel height, or `None` when the
+    /// file doesn't specify it.
+    pub fn pixel_aspect_ratio_f64(&self) -> Option<f64> {
+        match self.pixel_aspect_ratio_raw {
+            0 => None,
+            raw => Some((raw as f64 + 15.0) / 64.0),
+        }
+    }
+
+    /// Returns the raw pixel aspect ratio byte.
+    #[deprecated(
+        since = "0.2.0",
+        note = "use the pixel_aspect_ratio_raw field, or pixel_aspect_ratio_f64 for the ratio itself"
+    )]
+    pub fn pixel_aspect_ratio(&self) -> u8 {
+        self.pixel_aspect_ratio_raw
+    }
+
+    /// Returns `2^color_resolution`, the number of intensities each of red,
+    /// green and blue could take in the image the GIF was made from.
+    ///
+    /// GIF89a defines the field as one less than the number of bits per
+    /// primary color available to the original image; `color_resolution`
+    /// holds that number of bits. It describes the source, not this file:
+    /// the color tables can have more or fewer entries. Saturates at
+    /// `u16::MAX` for a resolution of 16 bits or more, which a decoded file
+    /// can't have.
+    pub fn color_count(&self) -> u16 {
+        1u16.checked_shl(self.color_resolution as u32)
+            .unwrap_or(u16::MAX)
+    }
+
+    /// Returns the number of colors the size field gives the global color
+    /// table, `2^(global_color_table_size_flag + 1)`. The field is set even
+    /// when there is no global color table. Only its low three bits count,
+    /// as only they are stored in the file.
+    pub fn actual_global_color_table_size(&self) -> usize {
+        2 << (self.global_color_table_size_flag & 0b111)
+    }
+
+    /// Returns the size of the global color table in bytes, three per color.
+    pub fn global_color_table_byte_count(&self) -> usize {
+        3 * self.actual_global_color_table_size()
+    }
+
+    /// Returns the size of the global color table in bytes.
+    #[deprecated(
+        since = "0.2.0",
+        note = "use global_color_table_byte_count, or actual_global_color_table_size for the number of colors"
+    )]
+    pub fn global_color_table_size(&self) -> usize {
+        self.global_color_table_byte_count()
+    }
+}
+
+impl ImageDescriptor {
+    /// Checks that the image lies within the canvas `lsd` describes, as the
+    /// spec requires, failing with [`GifError::MalformedBlock`] if it doesn't.
+    /// The error's offset is 0; the decoder moves it to where the descriptor
+    /// is in the file.
+    pub fn validate_against_lsd(&self, lsd: &LogicalScreenDescriptor) -> Result<(), GifError> {
+        if self.left as u32 + self.width as u32 > lsd.width as u32 {
+            return Err(GifError::MalformedBlock {
+                offset: 0,
+                message: "image extends past the right edge of the canvas",
+            });
+        }
+        if self.top as u32 + self.height as u32 > lsd.height as u32 {
+            return Err(GifError::MalformedBlock {
+                offset: 0,
+                message: "image extends past the bottom edge of the canvas",
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Frame {
+    /// Returns the color table the frame's pixels index into: its local color
+    /// table if it has one, otherwise `global`.
+    #[must_use = "this only reads the frame"]
+    pub fn effective_color_table<'a>(
+        &'a self,
+        global: Option<&'a ColorTable>,
+    ) -> Option<&'a ColorTable> {
+        self.local_color_table.as_ref().or(global)
+    }
+
+    /// Returns how long the frame is displayed, in hundredths of a second.
+    ///
+    /// An image without a graphic control extension, such as one part of a
+    /// multi-image still, is shown for no time at all.
+    #[must_use = "this only reads the frame"]
+    pub fn delay_centiseconds(&self) -> u16 {
+        self.graphic_control
+            .as_ref()
+            .map_or(0, |graphic_control| graphic_control.delay_centiseconds)
+    }
+
+    /// Returns what happens to the frame once it has been displayed.
+    ///
+    /// An image without a graphic control extension is left in place, so the
+    /// images of a multi-image still build up on the canvas.
+    #[must_use = "this only reads the frame"]
+    pub fn disposal_method(&self) -> &DisposalMethod {
+        self.graphic_control
+            .as_ref()
+            .map_or(&DisposalMethod::DoNotDispose, |graphic_control| {
+                &graphic_control.disposal_method
+            })
+    }
+
+    /// Returns the color table indices one row at a time, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        //`chunks` rejects a zero width; an image that wide has no pixels anyway.
+        self.pixels.chunks((self.descriptor.width as usize).max(1))
+    }
+
+    /// Returns the pixels as RGBA one row at a time, top to bottom, looking
+    /// colors up in `palette`.
+    ///
+    /// The `transparent` index, and indices outside the palette, become fully
+    /// transparent black.
+    pub fn rgba_rows<'a>(
+        &'a self,
+        palette: &'a ColorTable,
+        transparent: Option<u8>,
+    ) -> impl Iterator<Item = impl Iterator<Item = [u8; 4]> + 'a> + 'a {
+        self.rows().map(move |row| {
+            row.iter()
+                .map(move |&index| index_to_rgba(palette, transparent, index))
+        })
+    }
+}
+
+impl Gif {
+    /// Decodes a GIF from an open file.
+    #[cfg(feature = "std")]
+    #[deprecated(
+        since = "0.2.0",
+        note = "Use Gif::from_path or Gif::from_reader instead"
+    )]
+    pub fn from_file(f: &mut File) -> Result<Gif, GifError> {
+        Gif::from_reader(f)
+    }
+
+    /// Opens and decodes the GIF file at `path`.
+    #[cfg(feature = "std")]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Gif, GifError> {
+        let mut f = File::open(path).map_err(GifError::Io)?;
+        Gif::from_reader(&mut f)
+    }
+
+    /// Decodes a GIF held entirely in memory.
+    ///
+    /// ```
+    /// let data: &[u8] = include_bytes!("../tests/fixtures/sample.gif");
+    /// let gif = gif::Gif::from_bytes(data).unwrap();
+    /// assert_eq!(gif.dimensions(), (4, 2));
+    /// assert_eq!(gif.frame_count(), 2);
+    /// ```
+    pub fn from_bytes(data: &[u8]) -> Result<Gif, GifError> {
+        Gif::from_bytes_with_options(data, &DecodeOptions::default())
+    }
+
+    /// Decodes a GIF held entirely in memory, failing with
+    /// [`GifError::SizeLimitExceeded`] if it is larger than `options` allow.
+    pub fn from_bytes_with_options(data: &[u8], options: &DecodeOptions) -> Result<Gif, GifError> {
+        Gif::from_blocks_with_options(parser::split_blocks(data)?, options)
+    }
+
+    /// Decodes a GIF from any byte source.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: Read>(reader: &mut R) -> Result<Gif, GifError> {
+        Gif::from_reader_with_options(reader, &DecodeOptions::default())
+    }
+
+    /// Decodes a GIF from any byte source, failing with
+    /// [`GifError::SizeLimitExceeded`] if it is larger than `options` allow.
+    ///
+    /// The canvas size is checked before anything past the header is read.
+    #[cfg(feature = "std")]
+    pub fn from_reader_with_options<R: Read>(
+        reader: &mut R,
+        options: &DecodeOptions,
+    ) -> Result<Gif, GifError> {
+        let version = parse_header(reader)?;
+        let mut descriptor = [0; 7];
+        Gif::read_exact(reader, &mut descriptor)?;
+        let lsd = Gif::parse_logical_screen_descriptor(&descriptor)?;
+        options.check_canvas(&lsd)?;
+
+        //read the remaining blocks and split the whole file up.
+        let mut bytes = version.to_string().into_bytes();
+        bytes.extend_from_slice(&descriptor);
+        reader.read_to_end(&mut bytes).map_err(GifError::Io)?;
+        Gif::from_blocks_with_options(parser::split_blocks(&bytes)?, options)
+    }
+
+    /// Builds a GIF from its blocks in file order, as returned by
+    /// [`GifParser::parse_blocks`], decoding the extensions and images.
+    ///
+    /// The list must start with the header and screen descriptor, followed
+    /// by the global color table if there is one, and end with the trailer.
+    /// Anything after the trailer is ignored.
+    pub fn from_blocks(blocks: Vec<Block>) -> Result<Gif, GifError> {
+        Gif::from_blocks_with_options(blocks, &DecodeOptions::default())
+    }
+
+    /// Builds a GIF from its blocks as [`Gif::from_blocks`] does, failing
+    /// with [`GifError::SizeLimitExceeded`] if it is larger than `options`
+    /// allow.
+    ///
+    /// Each image is checked against the limits before it is decompressed.
+    pub fn from_blocks_with_options(
+        blocks: Vec<Block>,
+        options: &DecodeOptions,
+    ) -> Result<Gif, GifError> {
+        let mut blocks = blocks.into_iter().peekable();
+        let version = match blocks.next() {
+            Some(Block::Header(version)) => version,
+            _ => return Err(GifError::InvalidGifFile),
+        };
+        let lsd = match blocks.next() {
+            Some(Block::ScreenDescriptor(lsd)) => lsd,
+            _ => return Err(GifError::InvalidGifFile),
+        };
+        options.check_canvas(&lsd)?;
+        let global_color_table =
+            match blocks.next_if(|block| matches!(*block, Block::GlobalColorTable(_))) {
+                Some(Block::GlobalColorTable(table)) => Some(table),
+                _ => None,
+            };
+
+        let mut gif = Gif {
+            version,
+            lsd,
+            global_color_table,
+            image_descriptors: vec![],
+            frames: vec![],
+            loop_count: None,
+            comments: vec![],
+            plain_text_extensions: vec![],
+            unknown_extensions: vec![],
+        };
+        let mut graphic_control = None;
+        let mut total_pixels = 0;
+        for block in blocks {
+            match block {
+                Block::Image(image) => {
+                    let descriptor = &image.descriptor;
+                    check_limit(options.max_frames, gif.frames.len() + 1)?;
+                    total_pixels += descriptor.width as usize * descriptor.height as usize;
+                    check_limit(options.max_total_pixels, total_pixels)?;
+
+                    let pixels = Gif::decode_image_data(
+                        descriptor,
+                        image.min_code_size,
+                        image.sub_blocks().0,
+                    )
+                    .map_err(|e| e.offset_by(image.offset()))?;
+
+                    gif.image_descriptors.push(image.descriptor.clone());
+                    gif.frames.push(Frame {
+                        descriptor: image.descriptor,
+                        local_color_table: image.local_color_table,
+                        pixels,
+                        graphic_control: graphic_control.take(),
+                    });
+                }
+                Block::Extension(extension) => {
+                    let label = extension.label();
+                    let data = extension.sub_blocks();
+                    let raw_bytes = extension.raw_bytes();
+                    let offset = extension.offset();
+
+                    //extensions we don't understand are kept as raw data.
+                    match label {
+                        GRAPHIC_CONTROL_LABEL => {
+                            let extension = Gif::parse_graphic_control_extension(raw_bytes)
+                                .map_err(|e| e.offset_by(offset))?;
+                            graphic_control = Some(extension);
+                        }
+                        APPLICATION_LABEL => match Gif::parse_netscape_extension(data.clone()) {
+                            Some(netscape) => gif.loop_count = Some(netscape.loop_count),
+                            None => gif.unknown_extensions.push((label, raw_bytes.to_vec())),
+                        },
+                        COMMENT_LABEL => {
+                            //comments are informational, so bad text shouldn't fail the decode.
+                            let comment = String::from_utf8_lossy(raw_bytes).into_owned();
+                            gif.comments.push(comment);
+                        }
+                        PLAIN_TEXT_LABEL => {
+                            let plain_text = Gif::parse_plain_text_extension(data)
+                                .map_err(|e| e.offset_by(offset))?;
+                            gif.plain_text_extensions.push(plain_text);
+                            //a graphic control extension before plain text applies to the text.
+                            graphic_control = None;
+                        }
+                        _ => gif.unknown_extensions.push((label, raw_bytes.to_vec())),
+                    }
+                }
+                Block::Trailer => return Ok(gif),
+                _ => return Err(GifError::InvalidGifFile),
+            }
+        }
+        Err(GifError::UnexpectedEof)
+    }
+
+    /// Decodes a GIF from an async byte source, without blocking the runtime.
+    ///
+    /// The whole source is read into memory before anything is decoded.
+    #[cfg(feature = "async-tokio")]
+    pub fn from_async_reader<'a, R>(
+        reader: &'a mut R,
+    ) -> impl Future<Output = Result<Gif, GifError>> + 'a
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        use tokio::io::AsyncReadExt;
+
+        async move {
+            let mut bytes = vec![];
+            reader.read_to_end(&mut bytes).await.map_err(GifError::Io)?;
+            Gif::from_bytes(&bytes)
+        }
+    }
+
+    /// Reads the header and logical screen descriptor from `reader` and
+    /// returns the canvas dimensions as `(width, height)`.
+    ///
+    /// Exactly 13 bytes are read; the color tables and image data are left
+    /// in the reader.
+    #[cfg(feature = "std")]
+    pub fn peek_dimensions<R: Read>(reader: &mut R) -> Result<(u16, u16), GifError> {
+        parse_header(reader)?;
+        let mut buffer = [0; 7];
+        Gif::read_exact(reader, &mut buffer)?;
+        let lsd = Gif::parse_logical_screen_descriptor(&buffer)?;
+        Ok((lsd.width, lsd.height))
+    }
+
+    /// Encodes the GIF as GIF89a and writes it to `path`, replacing any
+    /// existing file.
+    ///
+    /// Every frame and extension is kept, though not necessarily in its
+    /// original position: the loop count, comments and unrecognized
+    /// extensions are written before the frames, and plain text extensions
+    /// after them.
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), GifError> {
+        let file = File::create(path).map_err(GifError::Io)?;
+        self.encode(io::BufWriter::new(file))?;
+        Ok(())
+    }
+
+    /// Encodes the GIF as [`Gif::save`] does and streams it to `writer`,
+    /// returning the number of bytes written.
+    ///
+    /// Blocks are written as they are encoded, one at a time, and `writer` is
+    /// flushed after the trailer.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<usize, GifError> {
+        let writer = self.encode(CountingWriter::new(writer))?;
+        Ok(writer.count)
+    }
+
+    /// Converts the GIF to an animated PNG with the same frame delays and
+    /// loop count.
+    ///
+    /// Each frame is composited, with disposal applied, and stored as a full
+    /// RGBA canvas. A GIF with no frames, or with an empty canvas, can't be
+    /// converted and fails with [`GifError::InvalidGifFile`].
+    #[cfg(feature = "apng")]
+    pub fn to_apng_bytes(&self) -> Result<Vec<u8>, GifError> {
+        apng::encode(self)
+    }
+
+    /// Encodes the GIF to `writer` as described for [`Gif::save`].
+    #[cfg(feature = "std")]
+    fn encode<W: Write>(&self, writer: W) -> Result<W, GifError> {
+        let mut encoder = GifEncoder::new(
+            writer,
+            self.lsd.width,
+            self.lsd.height,
+            self.global_color_table.as_ref(),
+        );
+        encoder.color_resolution = self.lsd.color_resolution;
+        encoder.background_color_index = self.lsd.background_color_index.unwrap_or(0);
+        encoder.pixel_aspect_ratio = self.lsd.pixel_aspect_ratio_raw;
+
+        if let Some(loop_count) = self.loop_count {
+            encoder.write_loop_count(loop_count)?;
+        }
+        for comment in &self.comments {
+            encoder.write_comment(comment)?;
+        }
+        for &(label, ref data) in &self.unknown_extensions {
+            encoder.write_extension(label, data)?;
+        }
+        for frame in &self.frames {
+            encoder.write_frame(frame)?;
+        }
+        for plain_text in &self.plain_text_extensions {
+            encoder.write_plain_text(plain_text)?;
+        }
+        encoder.finish()
+    }
+
+    /// Returns the number of images in the file.
+    #[must_use = "this only reads the GIF"]
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns an iterator over the frames in stream order, which can also run
+    /// backwards and knows how many frames are left.
+    pub fn iter_frames(&self) -> FrameIter<'_> {
+        FrameIter::new(&self.frames)
+    }
+
+    /// Returns the frames whose indices fall in `range`, clamped to the frames
+    /// there are, so bounds past the end give a shorter or empty slice
+    /// rather than a panic.
+    #[must_use = "this only reads the GIF"]
+    pub fn frames_in_range<R: RangeBounds<usize>>(&self, range: R) -> &[Frame] {
+        let len = self.frames.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        let start = start.min(len);
+        &self.frames[start..end.clamp(start, len)]
+    }
+
+    /// Returns whether the file holds more than one image.
+    #[must_use = "this only reads the GIF"]
+    pub fn is_animated(&self) -> bool {
+        self.frame_count() > 1
+    }
+
+    /// Returns a copy holding only the first frame and no loop count, for use
+    /// as a still image such as a thumbnail, or `None` if there are no frames.
+    ///
+    /// The screen descriptor, global color table and extensions other than
+    /// the loop count are kept as they are.
+    #[must_use = "this returns a new GIF and leaves the original unchanged"]
+    pub fn as_still_image(&self) -> Option<Gif> {
+        let first = self.frames.first()?;
+        Some(Gif {
+            version: self.version.clone(),
+            lsd: self.lsd.clone(),
+            global_color_table: self.global_color_table.clone(),
+            image_descriptors: vec![first.descriptor.clone()],
+            frames: vec![first.clone()],
+            loop_count: None,
+            comments: self.comments.clone(),
+            plain_text_extensions: self.plain_text_extensions.clone(),
+            unknown_extensions: self.unknown_extensions.clone(),
+        })
+    }
+
+    /// Returns the canvas size as `(width, height)`.
+    #[must_use = "this only reads the GIF"]
+    pub fn dimensions(&self) -> (u16, u16) {
+        (self.lsd.width, self.lsd.height)
+    }
+
+    /// Returns the background color, or `None` if there is no global color
+    /// table or the background index is outside it.
+    #[must_use = "this only reads the GIF"]
+    pub fn background_color(&self) -> Option<Color> {
+        let index = self.lsd.background_color_index?;
+        self.global_color_table.as_ref()?.get(index).cloned()
+    }
+
+    /// Returns whether any frame has a transparent color index, so that
+    /// compositing it over a background may leave pixels showing through.
+    #[must_use = "this only reads the GIF"]
+    pub fn contains_transparency(&self) -> bool {
+        (0..self.frames.len()).any(|index| self.frame_has_transparency(index))
+    }
+
+    /// Returns whether the frame at `index` has a transparent color index.
+    /// Returns `false` if there is no such frame.
+    ///
+    /// Only the graphic control extension is checked, not whether any pixel
+    /// actually uses the index.
+    #[must_use = "this only reads the GIF"]
+    pub fn frame_has_transparency(&self, index: usize) -> bool {
+        self.frames
+            .get(index)
+            .and_then(|frame| frame.graphic_control.as_ref())
+            .and_then(|graphic_control| graphic_control.transparent_color_index)
+            .is_some()
+    }
+
+    /// Returns how long the frame at `index` is displayed, in milliseconds,
+    /// as given by [`Frame::delay_centiseconds`], so a frame without a
+    /// graphic control extension has no delay.
+    ///
+    /// Returns `None` if there is no such frame.
+    #[must_use = "this only reads the GIF"]
+    pub fn frame_delay_ms(&self, index: usize) -> Option<f64> {
+        self.frames
+            .get(index)
+            .map(|frame| frame.delay_centiseconds() as f64 * 10.0)
+    }
+
+    /// Like [`Gif::frame_delay_ms`], but raises delays below `min_ms` to
+    /// `min_ms`, as browsers do for very short delays.
+    #[must_use = "this only reads the GIF"]
+    pub fn frame_delay_ms_normalized(&self, index: usize, min_ms: f64) -> Option<f64> {
+        self.frame_delay_ms(index).map(|delay| delay.max(min_ms))
+    }
+
+    /// Returns how long the animation plays for, in milliseconds, or infinity
+    /// if it loops forever.
+    ///
+    /// Each frame's delay is [`Gif::frame_delay_ms_normalized`] with the 20ms
+    /// minimum browsers apply, so frames without a delay count as 20ms. The
+    /// animation plays once plus once per repeat.
+    #[must_use = "this only reads the GIF"]
+    pub fn total_duration_ms(&self) -> f64 {
+        if self.frames.is_empty() {
+            return 0.0;
+        }
+        let once: f64 = (0..self.frames.len())
+            .filter_map(|index| self.frame_delay_ms_normalized(index, MIN_FRAME_DELAY_MS))
+            .sum();
+        match self.loop_count {
+            Some(0) => f64::INFINITY,
+            Some(repeats) => once * (repeats as f64 + 1.0),
+            None => once,
+        }
+    }
+
+    /// Returns the color table that applies to the frame at `index`: its local
+    /// color table if it has one, otherwise the global color table.
+    #[must_use = "this only reads the GIF"]
+    pub fn frame_color_table(&self, index: usize) -> Option<&ColorTable> {
+        let frame = self.frames.get(index)?;
+        frame.effective_color_table(self.global_color_table.as_ref())
+    }
+
+    /// Returns every distinct color in the global and local color tables, in
+    /// the order they first appear.
+    ///
+    /// If there are more than 256, only the 256 used by the most pixels are
+    /// kept, with ties going to the color that appears first. Transparent
+    /// pixels don't count as uses.
+    #[must_use = "this only reads the GIF"]
+    pub fn extract_palette(&self) -> ColorTable {
+        //each color's first position across the tables, and how many pixels use it.
+        let mut colors = BTreeMap::new();
+        let tables = self.global_color_table.iter().chain(
+            self.frames
+                .iter()
+                .filter_map(|frame| frame.local_color_table.as_ref()),
+        );
+        for table in tables {
+            for color in table.iter() {
+                let position = colors.len();
+                colors.entry(color.to_rgb()).or_insert((position, 0));
+            }
+        }
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            let table = match self.frame_color_table(index) {
+                Some(table) => table,
+                None => continue,
+            };
+            let mut uses = [0usize; 256];
+            for &pixel in &frame.pixels {
+                uses[pixel as usize] += 1;
+            }
+            let transparent = frame
+                .graphic_control
+                .as_ref()
+                .and_then(|graphic_control| graphic_control.transparent_color_index);
+            if let Some(transparent) = transparent {
+                uses[transparent as usize] = 0;
+            }
+            for (pixel, &count) in uses.iter().enumerate() {
+                if let Some(color) = table.get(pixel as u8) {
+                    colors.get_mut(&color.to_rgb()).unwrap().1 += count;
+                }
+            }
+        }
+
+        let mut colors: Vec<_> = colors.into_iter().collect();
+        if colors.len() > 256 {
+            colors.sort_by_key(|&(_, (position, count))| (Reverse(count), position));
+            colors.truncate(256);
+        }
+        colors.sort_by_key(|&(_, (position, _))| position);
+        ColorTable::new(
+            colors
+                .into_iter()
+                .map(|(rgb, _)| Color::new(rgb[0], rgb[1], rgb[2]))
+                .collect(),
+        )
+    }
+
+    /// Replaces the global color table, leaving every pixel's index as it is,
+    /// for effects such as palette cycling. Pixels with an index past the end
+    /// of a smaller table no longer have a color.
+    ///
+    /// Fails with [`GifError::InvalidColorTable`] if there is no global color
+    /// table or `table` has more colors than it.
+    pub fn replace_color_table(&mut self, table: ColorTable) -> Result<(), GifError> {
+        match self.global_color_table {
+            Some(ref current) if table.len() <= current.len() => {}
+            _ => return Err(GifError::InvalidColorTable),
+        }
+        self.lsd.global_color_table_size_flag = table.size_field();
+        self.global_color_table = Some(table);
+        Ok(())
+    }
+
+    /// Checks the structural invariants the decoder doesn't enforce, returning
+    /// every violation found.
+    ///
+    /// Frames must fit on the canvas, have a color table, hold exactly one
+    /// index per pixel and only refer to colors in their table, and the
+    /// background color must be in the global color table.
+    pub fn validate(&self) -> Result<(), Vec<GifError>> {
+        let mut errors = vec![];
+
+        if let Some(index) = self.lsd.background_color_index {
+            let table_len = self.global_color_table.as_ref().map_or(0, ColorTable::len);
+            if index as usize >= table_len {
+                errors.push(GifError::MalformedBlock {
+                    //the background color index is the sixth byte of the screen descriptor.
+                    offset: 6 + 5,
+                    message: "background color index is outside the global color table",
+                });
+            }
+        }
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            errors.extend(self.frame_errors(index, frame));
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            _ => Err(errors),
+        }
+    }
+
+    /// Adds `frame` to the end of the animation.
+    ///
+    /// Fails with [`GifError::InvalidFrame`], leaving the GIF unchanged, if
+    /// the frame would break one of the invariants [`Gif::validate`] checks.
+    pub fn append_frame(&mut self, frame: Frame) -> Result<(), GifError> {
+        let index = self.frames.len();
+        if let Some(error) = self.frame_errors(index, &frame).into_iter().next() {
+            return Err(error);
+        }
+        self.image_descriptors.push(frame.descriptor.clone());
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    /// Removes and returns the frame at `index`, failing with
+    /// [`GifError::OutOfBounds`] if there is no such frame.
+    ///
+    /// Later frames drawn over the removed one will be drawn over whatever
+    /// it covered instead.
+    pub fn remove_frame(&mut self, index: usize) -> Result<Frame, GifError> {
+        if index >= self.frames.len() {
+            return Err(GifError::OutOfBounds);
+        }
+        let frame = self.frames.remove(index);
+        self.image_descriptors = image_descriptors(&self.frames);
+        Ok(frame)
+    }
+
+    /// Replaces the frames in `range` with `replacement`, returning the frames
+    /// taken out.
+    ///
+    /// Fails with [`GifError::OutOfBounds`] if `range` isn't within the
+    /// frames, or with [`GifError::InvalidFrame`] if a replacement frame
+    /// would fail [`Gif::append_frame`]'s checks. Nothing changes on failure.
+    pub fn splice_frames(
+        &mut self,
+        range: Range<usize>,
+        replacement: Vec<Frame>,
+    ) -> Result<Vec<Frame>, GifError> {
+        if range.start > range.end || range.end > self.frames.len() {
+            return Err(GifError::OutOfBounds);
+        }
+        for (offset, frame) in replacement.iter().enumerate() {
+            if let Some(error) = self
+                .frame_errors(range.start + offset, frame)
+                .into_iter()
+                .next()
+            {
+                return Err(error);
+            }
+        }
+        let removed = self.frames.splice(range, replacement).collect();
+        self.image_descriptors = image_descriptors(&self.frames);
+        Ok(removed)
+    }
+
+    /// Returns the color at `(x, y)` on the canvas while the frame at
+    /// `frame_index` is displayed, with earlier frames composited beneath it.
+    ///
+    /// Parts of the canvas no frame has drawn on read as black.
+    pub fn pixel_at(&self, frame_index: usize, x: u16, y: u16) -> Result<Color, GifError> {
+        if frame_index >= self.frames.len() || x >= self.lsd.width || y >= self.lsd.height {
+            return Err(GifError::OutOfBounds);
+        }
+        let mut animator = Animator::new(self);
+        let canvas = animator.render_frame(frame_index);
+        let offset = (y as usize * self.lsd.width as usize + x as usize) * 4;
+        Ok(Color::new(
+            canvas[offset],
+            canvas[offset + 1],
+            canvas[offset + 2],
+        ))
+    }
+
+    /// Returns the mean color of the canvas while the frame at `index` is
+    /// displayed, rounded to the nearest intensity.
+    ///
+    /// Transparent pixels are left out; a canvas with no opaque pixels
+    /// averages to black, as in [`Gif::pixel_at`]. Fails with
+    /// [`GifError::OutOfBounds`] if there is no such frame.
+    pub fn average_frame_color(&self, index: usize) -> Result<Color, GifError> {
+        if index >= self.frames.len() {
+            return Err(GifError::OutOfBounds);
+        }
+        let mut animator = Animator::new(self);
+        let mut sums = [0u64; 3];
+        let mut count = 0u64;
+        for pixel in animator.render_frame(index).chunks(4) {
+            if pixel[3] != 0 {
+                for channel in 0..3 {
+                    sums[channel] += pixel[channel] as u64;
+                }
+                count += 1;
+            }
+        }
+        if count == 0 {
+            return Ok(Color::BLACK);
+        }
+        let mean = |channel: usize| ((sums[channel] + count / 2) / count) as u8;
+        Ok(Color::new(mean(0), mean(1), mean(2)))
+    }
+
+    /// Returns how many distinct colors the pixels of all the frames show.
+    ///
+    /// Unlike the lengths of the color tables, this doesn't count entries no
+    /// pixel uses, transparent pixels, or indices past the end of a frame's
+    /// color table. Equal colors in different tables count once.
+    #[must_use = "this only reads the GIF"]
+    pub fn unique_color_count(&self) -> usize {
+        let mut colors = BTreeSet::new();
+        for (index, frame) in self.frames.iter().enumerate() {
+            let table = match self.frame_color_table(index) {
+                Some(table) => table,
+                None => continue,
+            };
+            let mut used = [false; 256];
+            for &pixel in &frame.pixels {
+                used[pixel as usize] = true;
+            }
+            let transparent = frame
+                .graphic_control
+                .as_ref()
+                .and_then(|graphic_control| graphic_control.transparent_color_index);
+            if let Some(transparent) = transparent {
+                used[transparent as usize] = false;
+            }
+            for (pixel, _) in used.iter().enumerate().filter(|&(_, &used)| used) {
+                colors.extend(table.get(pixel as u8).map(|color| color.to_rgb()));
+            }
+        }
+        colors.len()
+    }
+
+    /// Gathers the figures in [`GifStats`] in one pass over the frames.
+    ///
+    /// A decoded GIF doesn't keep its compressed data, so finding
+    /// `compressed_size_bytes` compresses every frame again, which makes
+    /// this about as slow as encoding the file.
+    #[must_use = "this only reads the GIF"]
+    pub fn stats(&self) -> GifStats {
+        let mut palette = BTreeSet::new();
+        let global_colors = self.global_color_table.iter().flat_map(ColorTable::iter);
+        palette.extend(global_colors.map(|color| color.to_rgb()));
+        let mut total_pixels = 0;
+        let mut compressed_size_bytes = 0;
+        for frame in &self.frames {
+            let descriptor = &frame.descriptor;
+            total_pixels += descriptor.width as u64 * descriptor.height as u64;
+            let local_colors = frame.local_color_table.iter().flat_map(ColorTable::iter);
+            palette.extend(local_colors.map(|color| color.to_rgb()));
+
+            let table_len = frame
+                .effective_color_table(self.global_color_table.as_ref())
+                .map_or(0, |table| table.len().min(256));
+            let min_code_size = lzw::min_code_size(table_len, &frame.pixels);
+            compressed_size_bytes += 1 + lzw::encode(min_code_size, &frame.pixels).len();
+        }
+
+        GifStats {
+            frame_count: self.frame_count(),
+            canvas_width: self.lsd.width,
+            canvas_height: self.lsd.height,
+            total_pixels,
+            unique_palette_entries: palette.len(),
+            has_transparency: self.contains_transparency(),
+            total_duration_ms: self.total_duration_ms(),
+            compressed_size_bytes,
+        }
+    }
+
+    /// Counts how many pixels of the frame at `frame_index` use each color
+    /// table index, including indices past the end of its color table.
+    ///
+    /// Fails with [`GifError::OutOfBounds`] if there is no such frame.
+    pub fn histogram(&self, frame_index: usize) -> Result<[u32; 256], GifError> {
+        let frame = self.frames.get(frame_index).ok_or(GifError::OutOfBounds)?;
+        let mut histogram = [0; 256];
+        for &pixel in &frame.pixels {
+            histogram[pixel as usize] += 1;
+        }
+        Ok(histogram)
+    }
+
+    /// Returns whether any pixel of the frame at `frame_index` uses an index
+    /// that its color table, or the lack of one, has no color for. Returns
+    /// `false` if there is no such frame.
+    #[must_use = "this only reads the GIF"]
+    pub fn histogram_has_invalid_indices(&self, frame_index: usize) -> bool {
+        let table_len = self
+            .frame_color_table(frame_index)
+            .map_or(0, ColorTable::len);
+        match self.histogram(frame_index) {
+            Ok(histogram) => histogram.iter().skip(table_len).any(|&count| count > 0),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the RGBA pixels of the `w` by `h` rectangle at `(x, y)` on the
+    /// canvas while the frame at `frame_index` is displayed, row by row.
+    ///
+    /// Fails if the rectangle doesn't lie entirely within the canvas.
+    pub fn crop(
+        &self,
+        frame_index: usize,
+        x: u16,
+        y: u16,
+        w: u16,
+        h: u16,
+    ) -> Result<Vec<u8>, GifError> {
+        if frame_index >= self.frames.len()
+            || x as usize + w as usize > self.lsd.width as usize
+            || y as usize + h as usize > self.lsd.height as usize
+        {
+            return Err(GifError::OutOfBounds);
+        }
+        let mut animator = Animator::new(self);
+        let canvas = animator.render_frame(frame_index);
+        let mut rgba = Vec::with_capacity(w as usize * h as usize * 4);
+        for row in y as usize..y as usize + h as usize {
+            let start = (row * self.lsd.width as usize + x as usize) * 4;
+            rgba.extend_from_slice(&canvas[start..start + w as usize * 4]);
+        }
+        Ok(rgba)
+    }
+
+    /// Resizes the GIF to `new_width` by `new_height` pixels by nearest-neighbor
+    /// sampling, so that every composited frame is the original composite
+    /// resampled.
+    ///
+    /// Each frame is resampled over the part of the new canvas it covers,
+    /// keeping its color table, transparency, delay and disposal, so no color
+    /// is ever requantized. A frame that shrinks to nothing is kept, without
+    /// pixels, for its delay. Plain text extensions are left as they are.
+    ///
+    /// Fails with [`GifError::InvalidDimensions`] if either dimension is zero.
+    pub fn scale(&self, new_width: u16, new_height: u16) -> Result<Gif, GifError> {
+        if new_width == 0 || new_height == 0 {
+            return Err(GifError::InvalidDimensions);
+        }
+        let xs = nearest_samples(self.lsd.width, new_width);
+        let ys = nearest_samples(self.lsd.height, new_height);
+
+        let mut frames = Vec::with_capacity(self.frames.len());
+        for (index, frame) in self.frames.iter().enumerate() {
+            let descriptor = &frame.descriptor;
+            let (left, right) = covered(&xs, descriptor.left, descriptor.width);
+            let (top, bottom) = covered(&ys, descriptor.top, descriptor.height);
+            let width = descriptor.width as usize;
+            if frame.pixels.len() < width * descriptor.height as usize {
+                return Err(GifError::InvalidFrame {
+                    index,
+                    message: "image data is shorter than the frame",
+                });
+            }
+
+            let mut pixels = Vec::with_capacity((right - left) * (bottom - top));
+            for &y in &ys[top..bottom] {
+                let row = (y - descriptor.top as usize) * width;
+                for &x in &xs[left..right] {
+                    pixels.push(frame.pixels[row + x - descriptor.left as usize]);
+                }
+            }
+            frames.push(Frame {
+                descriptor: ImageDescriptor {
+                    left: left as u16,
+                    top: top as u16,
+                    width: (right - left) as u16,
+                    height: (bottom - top) as u16,
+                    is_interlaced: false,
+                    ..descriptor.clone()
+                },
+                pixels,
+                ..frame.clone()
+            });
+        }
+
+        Ok(Gif {
+            version: self.version.clone(),
+            lsd: LogicalScreenDescriptor {
+                width: new_width,
+                height: new_height,
+                ..self.lsd.clone()
+            },
+            global_color_table: self.global_color_table.clone(),
+            image_descriptors: image_descriptors(&frames),
+            frames,
+            loop_count: self.loop_count,
+            comments: self.comments.clone(),
+            plain_text_extensions: self.plain_text_extensions.clone(),
+            unknown_extensions: self.unknown_extensions.clone(),
+        })
+    }
+
+    /// Returns a copy that plays the animation backwards, with each frame
+    /// keeping its own delay and the loop count unchanged.
+    ///
+    /// Frames can depend on the ones drawn before them, so each composited
+    /// frame is stored whole, in the global color table if it has every color
+    /// needed and in a local one otherwise. Frames are left in place for the
+    /// next unless one would show through where the next has to be
+    /// transparent, in which case every frame is drawn on a clear canvas
+    /// instead. [`GifOptimizer`] can shrink the result.
+    #[must_use = "this returns a new GIF and leaves the original unchanged"]
+    pub fn reverse_frames(&self) -> Gif {
+        let mut animator = Animator::new(self);
+        let canvases: Vec<Vec<u8>> = (0..self.frames.len())
+            .map(|index| animator.render_frame(index).to_vec())
+            .rev()
+            .collect();
+
+        //a pixel can only stay transparent if it was transparent in the frame before.
+        let stacks = canvases.windows(2).all(|pair| {
+            pair[0]
+                .chunks(4)
+                .zip(pair[1].chunks(4))
+                .all(|(before, after)| after[3] != 0 || before[3] == 0)
+        });
+        let disposal_method = match stacks {
+            true => DisposalMethod::DoNotDispose,
+            _ => DisposalMethod::RestoreToPrevious,
+        };
+
+        let frames: Vec<Frame> = canvases
+            .iter()
+            .zip(self.frames.iter().rev())
+            .map(|(canvas, original)| {
+                let (pixels, local_color_table, transparent_color_index) =
+                    index_canvas(canvas, self.global_color_table.as_ref());
+                Frame {
+                    descriptor: ImageDescriptor {
+                        left: 0,
+                        top: 0,
+                        width: self.lsd.width,
+                        height: self.lsd.height,
+                        has_local_color_table: local_color_table.is_some(),
+                        is_interlaced: false,
+                        is_sorted: false,
+                        local_color_table_size: local_color_table
+                            .as_ref()
+                            .map_or(0, |table| 3 * (2 << table.size_field())),
+                    },
+                    local_color_table,
+                    pixels,
+                    graphic_control: Some(GraphicControlExtension {
+                        disposal_method: disposal_method.clone(),
+                        user_input: original
+                            .graphic_control
+                            .as_ref()
+                            .is_some_and(|graphic_control| graphic_control.user_input),
+                        transparent_color_index,
+                        delay_centiseconds: original.delay_centiseconds(),
+                    }),
+                }
+            })
+            .collect();
+
+        Gif {
+            version: self.version.clone(),
+            lsd: self.lsd.clone(),
+            global_color_table: self.global_color_table.clone(),
+            image_descriptors: image_descriptors(&frames),
+            frames,
+            loop_count: self.loop_count,
+            comments: self.comments.clone(),
+            plain_text_extensions: self.plain_text_extensions.clone(),
+            unknown_extensions: self.unknown_extensions.clone(),
+        }
+    }
+
+    /// Returns an animation that plays `self` and then `other`, with the loop
+    /// count, screen descriptor and extensions of `self` and the comments of
+    /// both. Every frame keeps its own delay.
+    ///
+    /// The global color tables are merged when their distinct colors fit in
+    /// one table. Frames of `other` that can't use the merged table, because
+    /// it is full or has no spare entry for their transparent color, get
+    /// `other`'s global color table as a local one, so no color changes.
+    ///
+    /// Fails with [`GifError::DimensionMismatch`] if the canvases differ in
+    /// size.
+    pub fn concat(&self, other: &Gif) -> Result<Gif, GifError> {
+        if self.dimensions() != other.dimensions() {
+            return Err(GifError::DimensionMismatch {
+                expected: self.dimensions(),
+                actual: other.dimensions(),
+            });
+        }
+
+        let mut table: Vec<Color> = self
+            .global_color_table
+            .iter()
+            .flat_map(ColorTable::iter)
+            .cloned()
+            .collect();
+        //where each of other's global colors ends up in the merged table.
+        let mut map = vec![];
+        for color in other.global_color_table.iter().flat_map(ColorTable::iter) {
+            let index = match table.iter().position(|merged| merged == color) {
+                Some(index) => index,
+                None => {
+                    table.push(*color);
+                    table.len() - 1
+                }
+            };
+            map.push(index);
+        }
+        let merged = match table.len() <= 256 {
+            true => Some(ColorTable::new(table)),
+            _ => None,
+        };
+
+        let mut frames = self.frames.clone();
+        for frame in &other.frames {
+            let frame = match (&frame.local_color_table, &other.global_color_table) {
+                (None, Some(global)) => merged
+                    .as_ref()
+                    .and_then(|merged| remap_frame(frame, &map, merged))
+                    .unwrap_or_else(|| Frame {
+                        descriptor: ImageDescriptor {
+                            has_local_color_table: true,
+                            local_color_table_size: 3 * (2 << global.size_field()),
+                            ..frame.descriptor.clone()
+                        },
+                        local_color_table: Some(global.clone()),
+                        ..frame.clone()
+                    }),
+                _ => frame.clone(),
+            };
+            frames.push(frame);
+        }
+
+        let global_color_table = match merged {
+            Some(merged) => Some(merged),
+            None => self.global_color_table.clone(),
+        };
+        let mut comments = self.comments.clone();
+        comments.extend(other.comments.iter().cloned());
+        let mut lsd = self.lsd.clone();
+        lsd.has_global_color_table = global_color_table.is_some();
+        lsd.global_color_table_size_flag = global_color_table
+            .as_ref()
+            .map_or(0, ColorTable::size_field);
+        Ok(Gif {
+            version: self.version.clone(),
+            lsd,
+            global_color_table,
+            image_descriptors: image_descriptors(&frames),
+            frames,
+            loop_count: self.loop_count,
+            comments,
+            plain_text_extensions: self.plain_text_extensions.clone(),
+            unknown_extensions: self.unknown_extensions.clone(),
+        })
+    }
+
+    /// Renders the first frame as RGBA, shrunk by nearest-neighbor sampling
+    /// until neither side is longer than `max_side`. Later frames are never
+    /// decoded.
+    ///
+    /// The aspect ratio is kept, rounding each side to the nearest pixel but
+    /// no lower than one. Canvases that already fit are returned at full size,
+    /// and a canvas with no pixels gives an empty thumbnail. Fails with
+    /// [`GifError::InvalidDimensions`] if `max_side` is zero and
+    /// [`GifError::InvalidGifFile`] if there are no frames.
+    pub fn thumbnail_rgba(&self, max_side: u16) -> Result<Vec<u8>, GifError> {
+        if max_side == 0 {
+            return Err(GifError::InvalidDimensions);
+        }
+        if self.frames.is_empty() {
+            return Err(GifError::InvalidGifFile);
+        }
+        let (width, height) = (self.lsd.width as usize, self.lsd.height as usize);
+        if width == 0 || height == 0 {
+            return Ok(Vec::new());
+        }
+        let longest = width.max(height);
+        let (new_width, new_height) = match longest > max_side as usize {
+            true => {
+                let side = |len: usize| ((len * max_side as usize + longest / 2) / longest).max(1);
+                (side(width), side(height))
+            }
+            _ => (width, height),
+        };
+
+        let mut animator = Animator::new(self);
+        let canvas = animator.render_frame(0);
+        let mut rgba = Vec::with_capacity(new_width * new_height * 4);
+        let xs = nearest_samples(width as u16, new_width as u16);
+        for y in nearest_samples(height as u16, new_height as u16) {
+            for &x in &xs {
+                let offset = (y * width + x) * 4;
+                rgba.extend_from_slice(&canvas[offset..offset + 4]);
+            }
+        }
+        Ok(rgba)
+    }
+
+    /// Compares the composited canvases of frames `a` and `b`, or returns
+    /// `None` if either index is out of range.
+    #[must_use = "this only reads the GIF"]
+    pub fn diff_frames(&self, a: usize, b: usize) -> Option<DiffResult> {
+        if a >= self.frames.len() || b >= self.frames.len() {
+            return None;
+        }
+        let mut animator = Animator::new(self);
+        let first = animator.render_frame(a.min(b)).to_vec();
+        let second = animator.render_frame(a.max(b));
+        Some(diff_canvases(&first, second, self.lsd.width as usize))
+    }
+
+    /// Returns the smallest rectangle holding every pixel the frame at `index`
+    /// draws onto the canvas, as `(left, top, width, height)` in canvas
+    /// coordinates.
+    ///
+    /// Transparent pixels, indices outside the color table and parts of the
+    /// frame past the edge of the canvas aren't drawn. Returns `None` if
+    /// there is no such frame or it draws nothing.
+    #[must_use = "this only reads the GIF"]
+    pub fn frame_bounding_box(&self, index: usize) -> Option<(u16, u16, u16, u16)> {
+        if index >= self.frames.len() {
+            return None;
+        }
+        let rgba = self.frame_to_rgba(index).ok()?;
+        let descriptor = &self.frames[index].descriptor;
+        let width = descriptor.width as usize;
+        let (mut left, mut top, mut right, mut bottom) = (usize::MAX, usize::MAX, 0, 0);
+        for (i, pixel) in rgba.chunks(4).enumerate() {
+            let x = descriptor.left as usize + i % width;
+            let y = descriptor.top as usize + i / width;
+            if pixel[3] != 0 && x < self.lsd.width as usize && y < self.lsd.height as usize {
+                left = left.min(x);
+                top = top.min(y);
+                right = right.max(x + 1);
+                bottom = bottom.max(y + 1);
+            }
+        }
+
+        match right {
+            0 => None,
+            _ => Some((
+                left as u16,
+                top as u16,
+                (right - left) as u16,
+                (bottom - top) as u16,
+            )),
+        }
+    }
+
+    /// Converts every frame to RGBA, one [`ColorWithAlpha`] per pixel.
+    ///
+    /// Each buffer covers only its frame's own rectangle, not the whole canvas.
+    /// The transparent color, and indices outside the color table, become
+    /// [`ColorWithAlpha::transparent`].
+    pub fn to_rgba_frames(&self) -> Result<Vec<Vec<ColorWithAlpha>>, GifError> {
+        (0..self.frames.len())
+            .map(|index| {
+                let (color_table, transparent_color_index) = self.frame_palette(index)?;
+                let frame = &self.frames[index];
+                let size = frame.descriptor.width as usize * frame.descriptor.height as usize;
+                let mut colors: Vec<ColorWithAlpha> = frame
+                    .pixels
+                    .iter()
+                    .map(|&color_index| {
+                        index_to_rgba(color_table, transparent_color_index, color_index).into()
+                    })
+                    .collect();
+                colors.resize(size, ColorWithAlpha::transparent());
+                Ok(colors)
+            })
+            .collect()
+    }
+
+    /// Returns the color table and transparent color index that frame
+    /// `index` is drawn with.
+    fn frame_palette(&self, index: usize) -> Result<(&ColorTable, Option<u8>), GifError> {
+        let color_table = self
+            .frame_color_table(index)
+            .ok_or(GifError::InvalidGifFile)?;
+        let transparent_color_index = self.frames[index]
+            .graphic_control
+            .as_ref()
+            .and_then(|graphic_control| graphic_control.transparent_color_index);
+        Ok((color_table, transparent_color_index))
+    }
+
+    fn frame_to_rgba(&self, index: usize) -> Result<Vec<u8>, GifError> {
+        let frame = &self.frames[index];
+        let (color_table, transparent_color_index) = self.frame_palette(index)?;
+
+        let size = frame.descriptor.width as usize * frame.descriptor.height as usize;
+        let mut rgba = Vec::with_capacity(size * 4);
+        for &color_index in &frame.pixels {
+            rgba.extend_from_slice(&index_to_rgba(
+                color_table,
+                transparent_color_index,
+                color_index,
+            ));
+        }
+        rgba.resize(size * 4, 0);
+        Ok(rgba)
+    }
+
+    /// Returns every way `frame`, at `index`, breaks the invariants of
+    /// [`Gif::validate`].
+    fn frame_errors(&self, index: usize, frame: &Frame) -> Vec<GifError> {
+        let mut errors = vec![];
+        let descriptor = &frame.descriptor;
+        if descriptor.validate_against_lsd(&self.lsd).is_err() {
+            errors.push(GifError::InvalidFrame {
+                index,
+                message: "frame extends past the canvas",
+            });
+        }
+
+        if frame.pixels.len() != descriptor.width as usize * descriptor.height as usize {
+            errors.push(GifError::InvalidFrame {
+                index,
+                message: "pixel data doesn't match the frame's dimensions",
+            });
+        }
+
+        match frame.effective_color_table(self.global_color_table.as_ref()) {
+            Some(table) => {
+                if frame
+                    .pixels
+                    .iter()
+                    .any(|&pixel| pixel as usize >= table.len())
+                {
+                    errors.push(GifError::InvalidFrame {
+                        index,
+                        message: "pixel refers to a color outside the color table",
+                    });
+                }
+            }
+            None => errors.push(GifError::InvalidFrame {
+                index,
+                message: "frame has no color table",
+            }),
+        }
+        errors
+    }
+
+    #[cfg(feature = "std")]
+    fn read_exact<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<(), GifError> {
+        reader.read_exact(buffer).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => GifError::UnexpectedEof,
+            _ => GifError::Io(e),
+        })
+    }
+
+    fn parse_version(bytes: &[u8; 6]) -> Result<GifVersion, GifError> {
+        if &bytes[0..3] != b"GIF" {
+            return Err(GifError::InvalidGifFile);
+        }
+
+        let version = match &bytes[3..6] {
+            b"87a" => GifVersion::V87a,
+            b"89a" => GifVersion::V89a,
+            unsupported => {
+                return Err(GifError::UnsupportedVersion(
+                    String::from_utf8_lossy(unsupported).into_owned(),
+                ))
+            }
+        };
+        Ok(version)
+    }
+
+    fn parse_logical_screen_descriptor(
+        bytes: &[u8; 7],
+    ) -> Result<LogicalScreenDescriptor, GifError> {
+        let width = ((bytes[1] as u16) << 8) | (bytes[0] as u16);
+        let height = ((bytes[3] as u16) << 8) | (bytes[2] as u16);
+
+        let packed_fields = bytes[4];
+        let has_global_color_table = (packed_fields & 0b10000000) == 0b10000000;
+        let is_global_color_table_sorted = (packed_fields & 0b00001000) == 0b00001000;
+
+        let color_resolution = ((bytes[4] & 0b01110000) >> 4) + 1u8;
+        let global_color_table_size_flag = bytes[4] & 0b00000111;
+
+        let background_color_index = match has_global_color_table {
+            true => Some(bytes[5]),
+            _ => None,
+        };
+
+        let pixel_aspect_ratio_raw = bytes[6];
+
+        Ok(LogicalScreenDescriptor {
+            width,
+            height,
+            has_global_color_table,
+            color_resolution,
+            is_global_color_table_sorted,
+            background_color_index,
+            global_color_table_size_flag,
+            pixel_aspect_ratio_raw,
+        })
+    }
+
+    fn parse_color_table(table: &[u8]) -> ColorTable {
+        let mut colors = Vec::with_capacity(table.len() / 3);
+        let mut i = 0;
+        while i < table.len() {
+            colors.push(Color {
+                red: table[i],
+                green: table[i + 1],
+                blue: table[i + 2],
+            });
+            i += 3;
+        }
+        ColorTable::new(colors)
+    }
+
+    /// Decompresses an image's sub-blocks into exactly `width * height`
+    /// pixels in top-to-bottom order. Extra data is dropped, and data that
+    /// ends early is padded with index 0.
+    fn decode_image_data(
+        descriptor: &ImageDescriptor,
+        min_code_size: u8,
+        sub_blocks: &[u8],
+    ) -> Result<Vec<u8>, GifError> {
+        let pixel_count = descriptor.width as usize * descriptor.height as usize;
+        let mut pixels = lzw::decode(min_code_size, sub_blocks, pixel_count)?;
+        pixels.resize(pixel_count, 0);
+        if descriptor.is_interlaced {
+            pixels = interlace::deinterlace(
+                &pixels,
+                descriptor.width as usize,
+                descriptor.height as usize,
+            );
+        }
+        Ok(pixels)
+    }
+
+    fn parse_image_descriptor(bytes: &[u8]) -> ImageDescriptor {
+        let packed_fields = bytes[8];
+        ImageDescriptor {
+            left: ((bytes[1] as u16) << 8) | (bytes[0] as u16),
+            top: ((bytes[3] as u16) << 8) | (bytes[2] as u16),
+            width: ((bytes[5] as u16) << 8) | (bytes[4] as u16),
+            height: ((bytes[7] as u16) << 8) | (bytes[6] as u16),
+            has_local_color_table: (packed_fields & 0b10000000) == 0b10000000,
+            is_interlaced: (packed_fields & 0b01000000) == 0b01000000,
+            is_sorted: (packed_fields & 0b00100000) == 0b00100000,
+            local_color_table_size: 3 * (1u16 << ((packed_fields & 0b00000111) + 1)),
+        }
+    }
+
+    fn parse_graphic_control_extension(bytes: &[u8]) -> Result<GraphicControlExtension, GifError> {
+        if bytes.len() < 4 {
+            return Err(GifError::MalformedBlock {
+                offset: 0,
+                message: "graphic control extension is too short",
+            });
+        }
+
+        let packed_fields = bytes[0];
+        let disposal_method = match (packed_fields & 0b00011100) >> 2 {
+            1 => DisposalMethod::DoNotDispose,
+            2 => DisposalMethod::RestoreToBackground,
+            3 => DisposalMethod::RestoreToPrevious,
+            other => DisposalMethod::Unspecified(other),
+        };
+        let user_input = (packed_fields & 0b00000010) == 0b00000010;
+        let transparent_color_index = match (packed_fields & 0b00000001) == 0b00000001 {
+            true => Some(bytes[3]),
+            _ => None,
+        };
+
+        Ok(GraphicControlExtension {
+            disposal_method,
+            user_input,
+            transparent_color_index,
+            delay_centiseconds: ((bytes[2] as u16) << 8) | (bytes[1] as u16),
+        })
+    }
+
+    /// Returns `None` for application extensions other than NETSCAPE2.0.
+    fn parse_netscape_extension(mut blocks: SubBlockReader) -> Option<NetscapeExtension> {
+        if blocks.next() != Some(b"NETSCAPE2.0") {
+            return None;
+        }
+        match blocks.next() {
+            Some(data) if data.len() >= 3 && data[0] == 1 => Some(NetscapeExtension {
+                loop_count: ((data[2] as u16) << 8) | (data[1] as u16),
+            }),
+            _ => None,
+        }
+    }
+
+    fn parse_plain_text_extension(
+        mut blocks: SubBlockReader,
+    ) -> Result<PlainTextExtension, GifError> {
+        let bytes = match blocks.next() {
+            Some(bytes) if bytes.len() >= 12 => bytes,
+            _ => {
+                return Err(GifError::MalformedBlock {
+                    offset: 0,
+                    message: "plain text extension header is too short",
+                })
+            }
+        };
+
+        Ok(PlainTextExtension {
+            text_grid_left: ((bytes[1] as u16) << 8) | (bytes[0] as u16),
+            text_grid_top: ((bytes[3] as u16) << 8) | (bytes[2] as u16),
+            text_grid_width: ((bytes[5] as u16) << 8) | (bytes[4] as u16),
+            text_grid_height: ((bytes[7] as u16) << 8) | (bytes[6] as u16),
+            cell_width: bytes[8],
+            cell_height: bytes[9],
+            foreground_color_index: bytes[10],
+            background_color_index: bytes[11],
+            text_data: String::from_utf8_lossy(&blocks.data()).into_owned(),
+        })
+    }
+
+    fn take(bytes: &[u8], start: usize, len: usize) -> Result<&[u8], GifError> {
+        bytes.get(start..start + len).ok_or(GifError::UnexpectedEof)
+    }
+}
+
+/// Returns the descriptor of each of `frames`, for [`Gif::image_descriptors`].
+fn image_descriptors(frames: &[Frame]) -> Vec<ImageDescriptor> {
+    frames
+        .iter()
+        .map(|frame| frame.descriptor.clone())
+        .collect()
+}
+
+/// Fails with [`GifError::SizeLimitExceeded`] if `actual` is over `limit`.
+fn check_limit(limit: usize, actual: usize) -> Result<(), GifError> {
+    match actual > limit {
+        true => Err(GifError::SizeLimitExceeded { limit, actual }),
+        _ => Ok(()),
+    }
+}
+
+/// Returns, for each of `to` positions along a resized axis, the position
+/// along the original `from`-long axis nearest its center.
+fn nearest_samples(from: u16, to: u16) -> Vec<usize> {
+    (0..to as usize)
+        .map(|i| (2 * i + 1) * from as usize / (2 * to as usize))
+        .collect()
+}
+
+/// Returns the range of resized positions sampling the `len` original
+/// positions from `start`.
+fn covered(samples: &[usize], start: u16, len: u16) -> (usize, usize) {
+    let end = start as usize + len as usize;
+    (
+        samples.partition_point(|&sample| sample < start as usize),
+        samples.partition_point(|&sample| sample < end),
+    )
+}
+
+/// Turns an RGBA canvas into color table indices, returning them with the
+/// local color table they need, if any, and the transparent index, if any
+/// pixel is transparent.
+///
+/// `global` is used if it has every opaque color and, when needed, an entry
+/// no opaque pixel uses to stand for transparency. Otherwise the canvas gets
+/// a local table of its exact colors, or a quantized one if there are too
+/// many.
+fn index_canvas(
+    canvas: &[u8],
+    global: Option<&ColorTable>,
+) -> (Vec<u8>, Option<ColorTable>, Option<u8>) {
+    let has_transparency = canvas.chunks(4).any(|pixel| pixel[3] == 0);
+    if let Some((pixels, transparent)) =
+        global.and_then(|table| index_with(canvas, table, has_transparency))
+    {
+        return (pixels, None, transparent);
+    }
+
+    let mut colors = BTreeMap::new();
+    for pixel in canvas.chunks(4).filter(|pixel| pixel[3] != 0) {
+        let next = colors.len();
+        colors.entry([pixel[0], pixel[1], pixel[2]]).or_insert(next);
+    }
+    let (mut table, mut pixels): (Vec<Color>, Vec<u8>) =
+        match colors.len() + has_transparency as usize <= 256 {
+            true => {
+                let mut table = vec![Color::BLACK; colors.len()];
+                for (&[red, green, blue], &index) in &colors {
+                    table[index] = Color::new(red, green, blue);
+                }
+                let pixels = canvas
+                    .chunks(4)
+                    .map(|pixel| match pixel[3] {
+                        0 => 0,
+                        _ => colors[&[pixel[0], pixel[1], pixel[2]]] as u8,
+                    })
+                    .collect();
+                (table, pixels)
+            }
+            _ => {
+                //leave room for the transparent entry.
+                let max_colors = match has_transparency {
+                    true => 128,
+                    _ => 256,
+                };
+                let (pixels, table) = quantize::quantize(canvas, max_colors);
+                (table.iter().cloned().collect(), pixels)
+            }
+        };
+
+    let transparent = match has_transparency {
+        true => {
+            let transparent = table.len() as u8;
+            table.push(Color::BLACK);
+            for (index, pixel) in pixels.iter_mut().zip(canvas.chunks(4)) {
+                if pixel[3] == 0 {
+                    *index = transparent;
+                }
+            }
+            Some(transparent)
+        }
+        _ => None,
+    };
+    (pixels, Some(ColorTable::new(table)), transparent)
+}
+
+/// Indexes `canvas` with `table`, or returns `None` if the table lacks one
+/// of its colors, or has no spare entry for the transparent pixels.
+fn index_with(
+    canvas: &[u8],
+    table: &ColorTable,
+    has_transparency: bool,
+) -> Option<(Vec<u8>, Option<u8>)> {
+    let mut indices = BTreeMap::new();
+    for (index, color) in table.iter().enumerate().take(256) {
+        indices.entry(color.to_rgb()).or_insert(index as u8);
+    }
+
+    let mut used = [false; 256];
+    let mut pixels = Vec::with_capacity(canvas.len() / 4);
+    for pixel in canvas.chunks(4) {
+        let index = match pixel[3] {
+            0 => 0,
+            _ => *indices.get(&[pixel[0], pixel[1], pixel[2]])?,
+        };
+        used[index as usize] |= pixel[3] != 0;
+        pixels.push(index);
+    }
+
+    if !has_transparency {
+        return Some((pixels, None));
+    }
+    let transparent = (0..table.len().min(256)).find(|&index| !used[index])? as u8;
+    for (index, pixel) in pixels.iter_mut().zip(canvas.chunks(4)) {
+        if pixel[3] == 0 {
+            *index = transparent;
+        }
+    }
+    Some((pixels, Some(transparent)))
+}
+
+/// Rewrites the pixels of `frame` through `map`, from its global color table
+/// to `merged`, or returns `None` if `merged` has no entry left for the
+/// frame's transparent color or for pixels outside the original table.
+fn remap_frame(frame: &Frame, map: &[usize], merged: &ColorTable) -> Option<Frame> {
+    let transparent = frame
+        .graphic_control
+        .as_ref()
+        .and_then(|graphic_control| graphic_control.transparent_color_index);
+    let mut used = vec![false; merged.len()];
+    let mut pixels = Vec::with_capacity(frame.pixels.len());
+    for &pixel in &frame.pixels {
+        //indices past the table have no color either way, so keep them past it.
+        let index = map.get(pixel as usize).map_or(merged.len(), |&index| index);
+        if index > 255 {
+            return None;
+        }
+        if Some(pixel) != transparent {
+            if let Some(used) = used.get_mut(index) {
+                *used = true;
+            }
+        }
+        pixels.push(index as u8);
+    }
+
+    let mut graphic_control = frame.graphic_control.clone();
+    if let Some(transparent) = transparent {
+        //the transparent entry may share its color with an opaque one, so it gets its own.
+        let spare = (0..merged.len()).find(|&index| !used[index])? as u8;
+        for (index, &pixel) in pixels.iter_mut().zip(&frame.pixels) {
+            if pixel == transparent {
+                *index = spare;
+            }
+        }
+        if let Some(ref mut graphic_control) = graphic_control {
+            graphic_control.transparent_color_index = Some(spare);
+        }
+    }
+    Some(Frame {
+        descriptor: frame.descriptor.clone(),
+        local_color_table: None,
+        pixels,
+        graphic_control,
+    })
+}
+
+/// Compares two RGBA canvases `width` pixels wide.
+fn diff_canvases(a: &[u8], b: &[u8], width: usize) -> DiffResult {
+    let mut changed_pixels = 0;
+    let (mut left, mut top, mut right, mut bottom) = (usize::MAX, usize::MAX, 0, 0);
+    for (i, (a, b)) in a.chunks(4).zip(b.chunks(4)).enumerate() {
+        if a != b {
+            let (x, y) = (i % width, i / width);
+            changed_pixels += 1;
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x + 1);
+            bottom = bottom.max(y + 1);
+        }
+    }
+
+    let bounding_box = match changed_pixels {
+        0 => (0, 0, 0, 0),
+        _ => (
+            left as u16,
+            top as u16,
+            (right - left) as u16,
+            (bottom - top) as u16,
+        ),
+    };
+    DiffResult {
+        changed_pixels,
+        bounding_box,
+    }
+}
+
+fn index_to_rgba(palette: &ColorTable, transparent: Option<u8>, index: u8) -> [u8; 4] {
+    match palette.get(index) {
+        Some(color) if Some(index) != transparent => color.to_rgba(0xff),
+        _ => color::TRANSPARENT_BLACK_RGBA,
+    }
+}
+
+/// Reads the 6-byte header from `reader` and returns the version it declares.
+///
+/// Nothing past the header is read, so this can be used to sniff a stream
+/// before deciding how to decode it.
+#[cfg(feature = "std")]
+pub fn parse_header<R: Read>(reader: &mut R) -> Result<GifVersion, GifError> {
+    let mut buffer = [0; 6];
+    Gif::read_exact(reader, &mut buffer)?;
+    Gif::parse_version(&buffer)
+}
+
+/// Encodes the GIF as GIF89a, as [`Gif::save`] does.
+///
+/// # Panics
+///
+/// Panics if a frame's pixel data doesn't match its dimensions, which can
+/// only happen to frames built or edited by hand: decoded frames always
+/// have one index per pixel.
+///
+/// ```
+/// use gif::{Color, ColorTable, FrameBuilder, Gif, GifBuilder};
+///
+/// let gif = GifBuilder::new(2, 1)
+///     .global_color_table(ColorTable::new(vec![Color::BLACK, Color::WHITE]))
+///     .add_frame(FrameBuilder::new(2, 1, vec![0, 1]))
+///     .build()
+///     .unwrap();
+/// let bytes = Vec::from(gif);
+/// assert!(bytes.starts_with(b"GIF89a"));
+/// assert_eq!(Gif::from_bytes(&bytes).unwrap().frames[0].pixels, [0, 1]);
+/// ```
+#[cfg(feature = "std")]
+impl From<Gif> for Vec<u8> {
+    fn from(gif: Gif) -> Vec<u8> {
+        gif.encode(vec![])
+            .expect("frame pixel data doesn't match its dimensions")
+    }
+}
+
+/// Decodes a GIF with [`Gif::from_bytes`].
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// let bytes = include_bytes!("../tests/fixtures/sample.gif").to_vec();
+/// let gif = gif::Gif::try_from(bytes).unwrap();
+/// assert_eq!(gif.dimensions(), (4, 2));
+///
+/// assert!(gif::Gif::try_from(b"not a gif".to_vec()).is_err());
+/// ```
+impl TryFrom<Vec<u8>> for Gif {
+    type Error = GifError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Gif, GifError> {
+        Gif::from_bytes(&bytes)
+    }
+}
+
+/// Parses `87a` or `89a`, with or without the `GIF` prefix and in any case.
+/// Anything else fails with [`GifError::UnsupportedVersion`].
+impl<'a> TryFrom<&'a str> for GifVersion {
+    type Error = GifError;
+
+    fn try_from(version: &'a str) -> Result<GifVersion, GifError> {
+        let lowercase = version.to_ascii_lowercase();
+        match lowercase.strip_prefix("gif").unwrap_or(&lowercase) {
+            "87a" => Ok(GifVersion::V87a),
+            "89a" => Ok(GifVersion::V89a),
+            _ => Err(GifError::UnsupportedVersion(String::from(version))),
+        }
+    }
+}
+
+impl fmt::Display for GifVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GifVersion::V87a => write!(f, "GIF87a"),
+            GifVersion::V89a => write!(f, "GIF89a"),
+        }
+    }
+}
+
+impl fmt::Display for Gif {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (width, height) = self.dimensions();
+        write!(
+            f,
+            "{} {}x{}, {} frame{}",
+            self.version,
+            width,
+            height,
+            self.frame_count(),
+            if self.frame_count() == 1 { "" } else { "s" }
+        )?;
+        match self.global_color_table {
+            Some(ref table) => write!(f, ", global color table of {} colors", table.len()),
+            None => write!(f, ", no global color table"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_color_table_size_is_a_power_of_two() {
+        let expected_bytes = [6, 12, 24, 48, 96, 192, 384, 768];
+        for (flag, &bytes) in expected_bytes.iter().enumerate() {
+            let descriptor = [1, 0, 1, 0, 0b1000_0000 | flag as u8, 0, 0];
+            let lsd = Gif::parse_logical_screen_descriptor(&descriptor).unwrap();
+            assert_eq!(lsd.global_color_table_size_flag, flag as u8);
+            assert_eq!(lsd.actual_global_color_table_size(), bytes / 3);
+            assert_eq!(lsd.global_color_table_byte_count(), bytes);
+        }
+    }
+
+    #[test]
+    fn color_resolution_is_shifted_down_from_bits_4_to_6() {
+        let descriptor = [1, 0, 1, 0, 0b0111_0000, 0, 0];
+        let lsd = Gif::parse_logical_screen_descriptor(&descriptor).unwrap();
+        assert_eq!(lsd.color_resolution, 8);
+        assert!(!lsd.has_global_color_table);
+        assert_eq!(lsd.global_color_table_size_flag, 0);
+    }
+}
@@ -0,0 +1,269 @@
+//! The variable-length-code LZW compression used for GIF image data.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::sub_block::{write_sub_blocks, SubBlockReader};
+use crate::GifError;
+
+const MAX_CODE_SIZE: u8 = 12;
+const MAX_CODES: usize = 1 << MAX_CODE_SIZE;
+
+const INVALID_CODE: GifError = GifError::MalformedBlock {
+    offset: 0,
+    message: "invalid LZW code",
+};
+
+/// A code table entry, stored as a link to the entry it extends.
+#[derive(Clone, Copy)]
+struct Entry {
+    prefix: u16,
+    suffix: u8,
+    first: u8,
+    len: u16,
+}
+
+/// Decompresses image data into at most `pixel_count` color table indices.
+///
+/// `sub_blocks` is the image data as stored in the file: a series of
+/// length-prefixed sub-blocks, optionally followed by the zero-length
+/// terminator. Decoding stops once `pixel_count` indices have been produced,
+/// at the end-of-information code, or at the end of the data if the stream
+/// never emits one, so the output never grows past what the image can hold.
+/// Errors report offsets of zero; the position of individual codes isn't
+/// tracked.
+pub fn decode(
+    min_code_size: u8,
+    sub_blocks: &[u8],
+    pixel_count: usize,
+) -> Result<Vec<u8>, GifError> {
+    if !(2..MAX_CODE_SIZE).contains(&min_code_size) {
+        return Err(GifError::MalformedBlock {
+            offset: 0,
+            message: "invalid LZW minimum code size",
+        });
+    }
+
+    let data = SubBlockReader(sub_blocks).data();
+    let clear_code = 1u16 << min_code_size;
+    let end_code = clear_code + 1;
+
+    let mut table = Vec::with_capacity(MAX_CODES);
+    for i in 0..clear_code {
+        table.push(Entry {
+            prefix: 0,
+            suffix: i as u8,
+            first: i as u8,
+            len: 1,
+        });
+    }
+    //the clear and end-of-information codes never appear in the output.
+    let reserved = Entry {
+        prefix: 0,
+        suffix: 0,
+        first: 0,
+        len: 0,
+    };
+    table.push(reserved);
+    table.push(reserved);
+
+    let mut output = Vec::with_capacity(pixel_count.min(data.len() * 2));
+    let mut code_size = min_code_size + 1;
+    let mut previous: Option<u16> = None;
+
+    let mut bits = 0u32;
+    let mut bit_count = 0u8;
+    let mut bytes = data.iter();
+
+    loop {
+        //a single code can emit a long string, so trim whatever overshoots.
+        if output.len() >= pixel_count {
+            output.truncate(pixel_count);
+            return Ok(output);
+        }
+        while bit_count < code_size {
+            match bytes.next() {
+                Some(&byte) => {
+                    bits |= (byte as u32) << bit_count;
+                    bit_count += 8;
+                }
+                None => return Ok(output),
+            }
+        }
+        let code = (bits & ((1 << code_size) - 1)) as u16;
+        bits >>= code_size;
+        bit_count -= code_size;
+
+        if code == clear_code {
+            table.truncate(end_code as usize + 1);
+            code_size = min_code_size + 1;
+            previous = None;
+            continue;
+        }
+        if code == end_code {
+            return Ok(output);
+        }
+
+        let previous_code = match previous {
+            Some(previous_code) => previous_code,
+            None => {
+                if code >= clear_code {
+                    return Err(INVALID_CODE);
+                }
+                emit(&table, code, &mut output);
+                previous = Some(code);
+                continue;
+            }
+        };
+
+        let first = if (code as usize) < table.len() {
+            emit(&table, code, &mut output);
+            table[code as usize].first
+        } else if code as usize == table.len() {
+            //the code being defined right now: the previous string plus its own first byte.
+            let first = table[previous_code as usize].first;
+            emit(&table, previous_code, &mut output);
+            output.push(first);
+            first
+        } else {
+            return Err(INVALID_CODE);
+        };
+
+        if table.len() < MAX_CODES {
+            let prefix = table[previous_code as usize];
+            table.push(Entry {
+                prefix: previous_code,
+                suffix: first,
+                first: prefix.first,
+                len: prefix.len + 1,
+            });
+            if table.len() == 1 << code_size && code_size < MAX_CODE_SIZE {
+                code_size += 1;
+            }
+        }
+        previous = Some(code);
+    }
+}
+
+/// Compresses color table indices into image data.
+///
+/// The result is a run of sub-blocks followed by the terminator, ready to be
+/// written after the minimum code size byte. Only the low `min_code_size`
+/// bits of each index are kept. The code table is cleared and rebuilt
+/// whenever it fills up.
+///
+/// # Panics
+///
+/// Panics if `min_code_size` is outside `2..12`.
+pub fn encode(min_code_size: u8, pixels: &[u8]) -> Vec<u8> {
+    assert!(
+        (2..MAX_CODE_SIZE).contains(&min_code_size),
+        "invalid LZW minimum code size"
+    );
+
+    let clear_code = 1u16 << min_code_size;
+    let end_code = clear_code + 1;
+    let mask = (clear_code - 1) as u8;
+
+    //maps a string, as the code of its prefix and its last index, to its code.
+    let mut table: BTreeMap<(u16, u8), u16> = BTreeMap::new();
+    let mut next_code = end_code + 1;
+    let mut code_size = min_code_size + 1;
+
+    let mut writer = BitWriter::default();
+    writer.write(clear_code, code_size);
+
+    let mut pixels = pixels.iter().map(|&pixel| pixel & mask);
+    if let Some(first) = pixels.next() {
+        let mut current = first as u16;
+        for pixel in pixels {
+            if let Some(&code) = table.get(&(current, pixel)) {
+                current = code;
+                continue;
+            }
+
+            writer.write(current, code_size);
+            if (next_code as usize) < MAX_CODES {
+                table.insert((current, pixel), next_code);
+                next_code += 1;
+                //the decoder lags a code behind, so it widens one code later.
+                if next_code - 1 == 1 << code_size && code_size < MAX_CODE_SIZE {
+                    code_size += 1;
+                }
+            } else {
+                writer.write(clear_code, code_size);
+                table.clear();
+                next_code = end_code + 1;
+                code_size = min_code_size + 1;
+            }
+            current = pixel as u16;
+        }
+        writer.write(current, code_size);
+        if next_code == 1 << code_size && code_size < MAX_CODE_SIZE {
+            code_size += 1;
+        }
+    }
+    writer.write(end_code, code_size);
+
+    let mut output = Vec::with_capacity(writer.bytes.len() + writer.bytes.len() / 255 + 2);
+    write_sub_blocks(&writer.finish(), &mut output);
+    output
+}
+
+/// Returns the smallest LZW minimum code size that covers both the color
+/// table and every index in `pixels`.
+pub(crate) fn min_code_size(table_len: usize, pixels: &[u8]) -> u8 {
+    let colors = pixels
+        .iter()
+        .map(|&pixel| pixel as usize + 1)
+        .max()
+        .unwrap_or(0)
+        .max(table_len);
+    let mut min_code_size = 2;
+    while (1 << min_code_size) < colors {
+        min_code_size += 1;
+    }
+    min_code_size
+}
+
+/// Packs variable-width codes into bytes, least significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: u32,
+    bit_count: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, code_size: u8) {
+        self.bits |= (code as u32) << self.bit_count;
+        self.bit_count += code_size;
+        while self.bit_count >= 8 {
+            self.bytes.push(self.bits as u8);
+            self.bits >>= 8;
+            self.bit_count -= 8;
+        }
+    }
+
+    /// Flushes any partial byte and returns the packed codes.
+    fn finish(mut self) -> Vec<u8> {
+        if self.bit_count > 0 {
+            self.bytes.push(self.bits as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Appends the string for `code` to `output`.
+fn emit(table: &[Entry], code: u16, output: &mut Vec<u8>) {
+    let len = table[code as usize].len as usize;
+    let start = output.len();
+    output.resize(start + len, 0);
+
+    let mut code = code;
+    for i in (start..start + len).rev() {
+        let entry = table[code as usize];
+        output[i] = entry.suffix;
+        code = entry.prefix;
+    }
+}
@@ -0,0 +1,171 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{
+    diff_canvases, lzw, Animator, DisposalMethod, Frame, Gif, GraphicControlExtension,
+    ImageDescriptor,
+};
+
+/// Shrinks animations by redrawing only what changes between frames.
+pub struct GifOptimizer;
+
+impl GifOptimizer {
+    /// Returns a copy of `gif` in which every frame after the first covers
+    /// only the rectangle that differs from the frame before, with unchanged
+    /// pixels inside it left transparent where that compresses better. Every
+    /// frame is left in place for the next, so the composited frames are
+    /// unchanged. Frames that are already smaller than their delta are kept.
+    ///
+    /// A delta can't be built if the frame's color table lacks a color that
+    /// shows through from an earlier frame, or if it would need to erase
+    /// pixels back to transparent. If a frame has no delta and can't be kept
+    /// either, `gif` is returned unchanged.
+    pub fn optimize(gif: &Gif) -> Gif {
+        if gif.frames.is_empty() {
+            return gif.clone();
+        }
+
+        let mut animator = Animator::new(gif);
+        let mut previous = animator.render_frame(0).to_vec();
+        let mut first = gif.frames[0].clone();
+        set_disposal_method(&mut first);
+        let mut frames = vec![first];
+
+        for index in 1..gif.frames.len() {
+            let canvas = animator.render_frame(index).to_vec();
+            //a frame drawn over an undisposed one already turns one canvas into the next.
+            let original = match *gif.frames[index - 1].disposal_method() {
+                DisposalMethod::RestoreToBackground | DisposalMethod::RestoreToPrevious => None,
+                _ => Some(gif.frames[index].clone()),
+            };
+            let mut frame = match (delta_frame(gif, index, &previous, &canvas), original) {
+                (Some(delta), Some(original)) => {
+                    match encoded_len(&delta.pixels) < encoded_len(&original.pixels) {
+                        true => delta,
+                        _ => original,
+                    }
+                }
+                (Some(frame), None) | (None, Some(frame)) => frame,
+                (None, None) => return gif.clone(),
+            };
+            set_disposal_method(&mut frame);
+            frames.push(frame);
+            previous = canvas;
+        }
+
+        let mut optimized = gif.clone();
+        optimized.image_descriptors = frames
+            .iter()
+            .map(|frame| frame.descriptor.clone())
+            .collect();
+        optimized.frames = frames;
+        optimized
+    }
+}
+
+/// Makes `frame` stay on the canvas once drawn.
+fn set_disposal_method(frame: &mut Frame) {
+    let graphic_control = frame
+        .graphic_control
+        .get_or_insert(GraphicControlExtension {
+            disposal_method: DisposalMethod::DoNotDispose,
+            user_input: false,
+            transparent_color_index: None,
+            delay_centiseconds: 0,
+        });
+    graphic_control.disposal_method = DisposalMethod::DoNotDispose;
+}
+
+/// Builds a frame that turns the `previous` canvas into `canvas` when drawn
+/// over it, using the color table of the frame at `index`.
+fn delta_frame(gif: &Gif, index: usize, previous: &[u8], canvas: &[u8]) -> Option<Frame> {
+    let width = gif.lsd.width as usize;
+    let original = &gif.frames[index];
+    let color_table = gif.frame_color_table(index)?;
+    let given_transparent_color_index = original
+        .graphic_control
+        .as_ref()
+        .and_then(|graphic_control| graphic_control.transparent_color_index);
+
+    let mut indices = BTreeMap::new();
+    for (i, color) in color_table.iter().enumerate().take(256) {
+        if Some(i as u8) != given_transparent_color_index {
+            indices.entry(color.to_rgb()).or_insert(i as u8);
+        }
+    }
+
+    //an unchanged frame still needs an image to carry its delay.
+    let (left, top, w, h) = match diff_canvases(previous, canvas, width).bounding_box {
+        (_, _, 0, 0) => (0, 0, 1, 1),
+        bounding_box => bounding_box,
+    };
+
+    //without a transparent index of its own, the frame borrows one its pixels don't use.
+    let transparent_color_index = given_transparent_color_index.or_else(|| {
+        let mut used = [false; 256];
+        for y in top as usize..top as usize + h as usize {
+            let row = (y * width + left as usize) * 4..(y * width + (left + w) as usize) * 4;
+            for pixel in canvas[row].chunks(4) {
+                if let Some(&index) = indices.get(&[pixel[0], pixel[1], pixel[2]]) {
+                    used[index as usize] = true;
+                }
+            }
+        }
+        (0..color_table.len().min(256))
+            .find(|&i| !used[i])
+            .map(|i| i as u8)
+    });
+
+    //unchanged pixels are made transparent, or repainted if that's smaller.
+    let area = w as usize * h as usize;
+    let mut punched = Vec::with_capacity(area);
+    let mut repainted = Some(Vec::with_capacity(area));
+    for y in top as usize..top as usize + h as usize {
+        for x in left as usize..left as usize + w as usize {
+            let offset = (y * width + x) * 4;
+            let before = &previous[offset..offset + 4];
+            let after = &canvas[offset..offset + 4];
+            let opaque = match after[3] {
+                0 => None,
+                _ => indices.get(&[after[0], after[1], after[2]]).cloned(),
+            };
+            let index = match before == after {
+                true => transparent_color_index.or(opaque),
+                _ => opaque,
+            };
+            punched.push(index?);
+            repainted = repainted.and_then(|mut pixels| {
+                pixels.push(opaque?);
+                Some(pixels)
+            });
+        }
+    }
+    let pixels = match repainted {
+        Some(repainted) if encoded_len(&repainted) < encoded_len(&punched) => repainted,
+        _ => punched,
+    };
+
+    let mut frame = Frame {
+        descriptor: ImageDescriptor {
+            left,
+            top,
+            width: w,
+            height: h,
+            is_interlaced: false,
+            ..original.descriptor.clone()
+        },
+        local_color_table: original.local_color_table.clone(),
+        pixels,
+        graphic_control: original.graphic_control.clone(),
+    };
+    set_disposal_method(&mut frame);
+    if let Some(ref mut graphic_control) = frame.graphic_control {
+        graphic_control.transparent_color_index = transparent_color_index;
+    }
+    Some(frame)
+}
+
+/// Returns how many bytes `pixels` take up once compressed.
+fn encoded_len(pixels: &[u8]) -> usize {
+    lzw::encode(lzw::min_code_size(0, pixels), pixels).len()
+}
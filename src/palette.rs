@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+
+use crate::{Color, ColorTable};
+
+/// A color table indexed for fast nearest-color queries.
+///
+/// The colors are kept sorted along one axis, so a query only measures the
+/// colors that are close enough along that axis to beat the best match so
+/// far. Distances are Euclidean in RGB space or, with the `lab` feature, in
+/// CIE L\*a\*b\* space, where they follow perceived differences more closely.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    table: ColorTable,
+    /// The coordinate the colors are sorted by: the one they spread widest along.
+    axis: usize,
+    /// The coordinates and index of every color, sorted along `axis`.
+    sorted: Vec<([f32; 3], u8)>,
+}
+
+impl Palette {
+    /// Indexes `table`. Colors past the 256th can't be referred to by a
+    /// `u8` index and are left out of queries.
+    pub fn new(table: ColorTable) -> Palette {
+        let mut sorted: Vec<_> = table
+            .iter()
+            .take(256)
+            .enumerate()
+            .map(|(index, color)| (coordinates(color), index as u8))
+            .collect();
+        let spread = |axis: usize| {
+            let values = sorted.iter().map(|entry| entry.0[axis]);
+            values.clone().fold(f32::MIN, f32::max) - values.fold(f32::MAX, f32::min)
+        };
+        let axis = (0..3)
+            .max_by(|&a, &b| spread(a).total_cmp(&spread(b)))
+            .unwrap();
+        sorted.sort_by(|a, b| a.0[axis].total_cmp(&b.0[axis]));
+        Palette {
+            table,
+            axis,
+            sorted,
+        }
+    }
+
+    /// Returns the underlying color table.
+    pub fn color_table(&self) -> &ColorTable {
+        &self.table
+    }
+
+    /// Returns the index and value of the entry closest to the given color.
+    /// Ties go to the lowest index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette is empty.
+    pub fn nearest_color(&self, r: u8, g: u8, b: u8) -> (u8, &Color) {
+        let target = coordinates(&Color::new(r, g, b));
+        let start = self
+            .sorted
+            .partition_point(|&(coordinates, _)| coordinates[self.axis] < target[self.axis]);
+
+        let mut best = None;
+        search(self.sorted[start..].iter(), self.axis, target, &mut best);
+        search(
+            self.sorted[..start].iter().rev(),
+            self.axis,
+            target,
+            &mut best,
+        );
+
+        let (_, index) = best.expect("nearest color of an empty palette");
+        (index, self.table.get(index).unwrap())
+    }
+}
+
+/// Walks `entries` away from `target` along the sorted axis, updating `best`
+/// with any closer color.
+fn search<'a, I>(entries: I, axis: usize, target: [f32; 3], best: &mut Option<(f32, u8)>)
+where
+    I: Iterator<Item = &'a ([f32; 3], u8)>,
+{
+    for &(coordinates, index) in entries {
+        let along_axis = coordinates[axis] - target[axis];
+        //nothing further along the axis can be any closer.
+        if let Some((distance, _)) = *best {
+            if along_axis * along_axis > distance {
+                return;
+            }
+        }
+        let distance = squared_distance(coordinates, target);
+        match *best {
+            Some(current) if current <= (distance, index) => {}
+            _ => *best = Some((distance, index)),
+        }
+    }
+}
+
+fn squared_distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let d0 = a[0] - b[0];
+    let d1 = a[1] - b[1];
+    let d2 = a[2] - b[2];
+    d0 * d0 + d1 * d1 + d2 * d2
+}
+
+#[cfg(not(feature = "lab"))]
+fn coordinates(color: &Color) -> [f32; 3] {
+    [color.red as f32, color.green as f32, color.blue as f32]
+}
+
+/// Converts an sRGB color to CIE L\*a\*b\* under the D65 white point.
+#[cfg(feature = "lab")]
+fn coordinates(color: &Color) -> [f32; 3] {
+    fn linear(channel: u8) -> f32 {
+        let c = channel as f32 / 255.0;
+        match c <= 0.04045 {
+            true => c / 12.92,
+            _ => ((c + 0.055) / 1.055).powf(2.4),
+        }
+    }
+    fn f(t: f32) -> f32 {
+        match t > 216.0 / 24389.0 {
+            true => t.cbrt(),
+            _ => (24389.0 / 27.0 * t + 16.0) / 116.0,
+        }
+    }
+
+    let (r, g, b) = (linear(color.red), linear(color.green), linear(color.blue));
+    let x = f((0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047);
+    let y = f(0.2126 * r + 0.7152 * g + 0.0722 * b);
+    let z = f((0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883);
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
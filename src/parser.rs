@@ -0,0 +1,191 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::prelude::*;
+
+use crate::{
+    ColorTable, Gif, GifError, GifVersion, ImageDescriptor, LogicalScreenDescriptor,
+    SubBlockReader, EXTENSION_INTRODUCER, IMAGE_SEPARATOR, TRAILER,
+};
+
+/// A top-level block of a GIF file, as split off by [`GifParser`].
+///
+/// [`Gif::from_blocks`] interprets a list of blocks in file order.
+#[derive(Debug, Clone)]
+pub enum Block {
+    /// The version from the header. Always the first block.
+    Header(GifVersion),
+    /// The logical screen descriptor.
+    ScreenDescriptor(LogicalScreenDescriptor),
+    /// The global color table, if the file has one.
+    GlobalColorTable(ColorTable),
+    /// An extension, with its data undecoded.
+    Extension(ExtensionBlock),
+    /// An image, with its pixels still compressed.
+    Image(ImageBlock),
+    /// The trailer. Always the last block.
+    Trailer,
+}
+
+/// An extension block whose data hasn't been interpreted.
+#[derive(Debug, Clone)]
+pub struct ExtensionBlock {
+    label: u8,
+    /// The sub-blocks with their length bytes, but not the terminator.
+    sub_blocks: Vec<u8>,
+    /// The data of the sub-blocks, concatenated.
+    data: Vec<u8>,
+    /// Byte offset of the sub-blocks in the file, for error reporting.
+    offset: usize,
+}
+
+/// An image block whose pixel data hasn't been decompressed.
+#[derive(Debug, Clone)]
+pub struct ImageBlock {
+    /// Where the image sits on the canvas.
+    pub descriptor: ImageDescriptor,
+    /// The color table that applies to this image only, if it has one.
+    pub local_color_table: Option<ColorTable>,
+    /// The LZW minimum code size the pixel data was compressed with.
+    pub min_code_size: u8,
+    /// The sub-blocks with their length bytes, but not the terminator.
+    sub_blocks: Vec<u8>,
+    /// Byte offset of the sub-blocks in the file, for error reporting.
+    offset: usize,
+}
+
+impl ExtensionBlock {
+    /// Returns the label byte that identifies the kind of extension.
+    pub fn label(&self) -> u8 {
+        self.label
+    }
+
+    /// Returns an iterator over the extension's sub-blocks.
+    pub fn sub_blocks(&self) -> SubBlockReader<'_> {
+        SubBlockReader(&self.sub_blocks)
+    }
+
+    /// Returns the data of every sub-block, concatenated, exactly as it
+    /// appears in the file. This is what [`Gif::unknown_extensions`] keeps
+    /// for an extension the decoder doesn't interpret, so it can be written
+    /// back out unchanged.
+    pub fn raw_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub(crate) fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl ImageBlock {
+    /// Returns an iterator over the compressed pixel data's sub-blocks.
+    pub fn sub_blocks(&self) -> SubBlockReader<'_> {
+        SubBlockReader(&self.sub_blocks)
+    }
+
+    pub(crate) fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Splits a GIF into its blocks without decoding any image data.
+#[cfg(feature = "std")]
+pub struct GifParser;
+
+#[cfg(feature = "std")]
+impl GifParser {
+    /// Reads a GIF from `reader` and returns its blocks in file order, from
+    /// the header to the trailer.
+    ///
+    /// Only the block structure is checked; extension data and pixels are
+    /// left for [`Gif::from_blocks`] to decode.
+    pub fn parse_blocks<R: Read>(reader: &mut R) -> Result<Vec<Block>, GifError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).map_err(GifError::Io)?;
+        split_blocks(&bytes)
+    }
+}
+
+/// Splits a GIF held in memory into its blocks.
+pub(crate) fn split_blocks(bytes: &[u8]) -> Result<Vec<Block>, GifError> {
+    let mut header = [0; 6];
+    header.copy_from_slice(Gif::take(bytes, 0, 6)?);
+    let mut blocks = vec![Block::Header(Gif::parse_version(&header)?)];
+
+    let mut descriptor = [0; 7];
+    descriptor.copy_from_slice(Gif::take(bytes, 6, 7)?);
+    let lsd = Gif::parse_logical_screen_descriptor(&descriptor)?;
+    let mut i = 13;
+    let global_color_table = match lsd.has_global_color_table {
+        true => {
+            let size = lsd.global_color_table_byte_count();
+            let table = Gif::parse_color_table(Gif::take(bytes, i, size)?);
+            i += size;
+            Some(table)
+        }
+        _ => None,
+    };
+    blocks.push(Block::ScreenDescriptor(lsd.clone()));
+    blocks.extend(global_color_table.map(Block::GlobalColorTable));
+
+    loop {
+        match bytes.get(i) {
+            Some(&IMAGE_SEPARATOR) => {
+                let descriptor = Gif::parse_image_descriptor(Gif::take(bytes, i + 1, 9)?);
+                descriptor
+                    .validate_against_lsd(&lsd)
+                    .map_err(|e| e.offset_by(i + 1))?;
+                i += 10;
+
+                let local_color_table = match descriptor.has_local_color_table {
+                    true => {
+                        let size = descriptor.local_color_table_size as usize;
+                        let table = Gif::parse_color_table(Gif::take(bytes, i, size)?);
+                        i += size;
+                        Some(table)
+                    }
+                    _ => None,
+                };
+
+                let min_code_size = *bytes.get(i).ok_or(GifError::UnexpectedEof)?;
+                let start = i + 1;
+                let (data, len) =
+                    SubBlockReader::split(&bytes[start..]).map_err(|e| e.offset_by(start))?;
+                i = start + len;
+
+                blocks.push(Block::Image(ImageBlock {
+                    descriptor,
+                    local_color_table,
+                    min_code_size,
+                    sub_blocks: data.0.to_vec(),
+                    offset: start,
+                }));
+            }
+            Some(&EXTENSION_INTRODUCER) => {
+                let label = *bytes.get(i + 1).ok_or(GifError::UnexpectedEof)?;
+                let start = i + 2;
+                let (data, len) =
+                    SubBlockReader::split(&bytes[start..]).map_err(|e| e.offset_by(start))?;
+                i = start + len;
+
+                blocks.push(Block::Extension(ExtensionBlock {
+                    label,
+                    sub_blocks: data.0.to_vec(),
+                    data: data.data(),
+                    offset: start,
+                }));
+            }
+            Some(&TRAILER) => {
+                blocks.push(Block::Trailer);
+                return Ok(blocks);
+            }
+            Some(_) => {
+                return Err(GifError::MalformedBlock {
+                    offset: i,
+                    message: "unknown block introducer",
+                })
+            }
+            None => return Err(GifError::UnexpectedEof),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Color quantization, for encoding full-color images with a color table.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{Color, ColorTable, Palette};
+
+/// Reduces RGBA pixels to at most `max_colors` colors by median cut.
+///
+/// Returns one color table index per pixel and the table itself. The alpha
+/// channel is ignored, and a trailing partial pixel is dropped.
+///
+/// # Panics
+///
+/// Panics if `max_colors` is not a power of two no greater than 256.
+pub fn quantize(pixels: &[u8], max_colors: usize) -> (Vec<u8>, ColorTable) {
+    assert!(
+        max_colors.is_power_of_two() && max_colors <= 256,
+        "max_colors must be a power of two no greater than 256"
+    );
+
+    let mut histogram = BTreeMap::new();
+    for pixel in pixels.chunks_exact(4) {
+        *histogram
+            .entry([pixel[0], pixel[1], pixel[2]])
+            .or_insert(0u32) += 1;
+    }
+
+    let mut boxes = vec![];
+    if !histogram.is_empty() {
+        boxes.push(histogram.into_iter().collect::<Vec<_>>());
+    }
+    while boxes.len() < max_colors {
+        //split the box spanning the widest range of any one channel.
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|&(_, colors)| colors.len() > 1)
+            .map(|(index, colors)| (index, widest_channel(colors)))
+            .max_by_key(|&(_, (_, range))| range);
+        let (index, channel) = match widest {
+            Some((index, (channel, _))) => (index, channel),
+            None => break,
+        };
+
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_by_key(|&(color, _)| color[channel]);
+        let split = median(&colors);
+        boxes.push(colors.split_off(split));
+        boxes.push(colors);
+    }
+
+    let table = ColorTable::new(boxes.iter().map(|colors| average(colors)).collect());
+    let palette = Palette::new(table);
+    let indices = pixels
+        .chunks_exact(4)
+        .map(|pixel| palette.nearest_color(pixel[0], pixel[1], pixel[2]).0)
+        .collect();
+    (indices, palette.color_table().clone())
+}
+
+/// Returns the channel along which `colors` vary most, and how much they vary.
+fn widest_channel(colors: &[([u8; 3], u32)]) -> (usize, u8) {
+    (0..3)
+        .map(|channel| {
+            let values = colors.iter().map(|&(color, _)| color[channel]);
+            (
+                channel,
+                values.clone().max().unwrap() - values.min().unwrap(),
+            )
+        })
+        .max_by_key(|&(_, range)| range)
+        .unwrap()
+}
+
+/// Returns where to split sorted `colors` so that each half holds about the
+/// same number of pixels. Both halves are non-empty.
+fn median(colors: &[([u8; 3], u32)]) -> usize {
+    let total: u64 = colors.iter().map(|&(_, count)| count as u64).sum();
+    let mut seen = 0;
+    for (i, &(_, count)) in colors.iter().enumerate() {
+        seen += count as u64;
+        if seen * 2 >= total {
+            return (i + 1).min(colors.len() - 1);
+        }
+    }
+    colors.len() - 1
+}
+
+/// Returns the pixel-weighted mean of `colors`.
+fn average(colors: &[([u8; 3], u32)]) -> Color {
+    let mut sums = [0u64; 3];
+    let mut total = 0u64;
+    for &(color, count) in colors {
+        for channel in 0..3 {
+            sums[channel] += color[channel] as u64 * count as u64;
+        }
+        total += count as u64;
+    }
+    let mean = |channel: usize| ((sums[channel] + total / 2) / total) as u8;
+    Color::new(mean(0), mean(1), mean(2))
+}
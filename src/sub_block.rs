@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+
+use crate::GifError;
+
+/// Iterates over the data of a run of sub-blocks.
+///
+/// Each sub-block is a length byte followed by that many data bytes; the run
+/// ends at a zero-length block. Iteration stops at the terminator, or early if
+/// the data runs out.
+#[derive(Debug, Clone)]
+pub struct SubBlockReader<'a>(pub &'a [u8]);
+
+impl<'a> SubBlockReader<'a> {
+    /// Splits the run of sub-blocks at the start of `bytes` off from what
+    /// follows it.
+    ///
+    /// Returns a reader over the run and the number of bytes it occupies,
+    /// including the terminator. Fails if a sub-block runs past the end of
+    /// `bytes` or the terminator is missing.
+    pub fn split(bytes: &'a [u8]) -> Result<(SubBlockReader<'a>, usize), GifError> {
+        let mut i = 0;
+        loop {
+            match bytes.get(i) {
+                Some(&0) => return Ok((SubBlockReader(&bytes[..i]), i + 1)),
+                Some(&len) => i += 1 + len as usize,
+                None => {
+                    return Err(GifError::MalformedBlock {
+                        offset: i,
+                        message: "sub-block runs past the end of the data",
+                    })
+                }
+            }
+        }
+    }
+
+    /// Concatenates the data of every remaining sub-block.
+    pub fn data(self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.0.len());
+        for block in self {
+            data.extend_from_slice(block);
+        }
+        data
+    }
+}
+
+impl<'a> Iterator for SubBlockReader<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let len = match self.0.first() {
+            Some(&0) | None => return None,
+            Some(&len) => len as usize,
+        };
+        match self.0.get(1..1 + len) {
+            Some(block) => {
+                self.0 = &self.0[1 + len..];
+                Some(block)
+            }
+            None => {
+                self.0 = &[];
+                None
+            }
+        }
+    }
+}
+
+/// Appends `data` to `out` as a run of sub-blocks of at most 255 bytes,
+/// followed by the terminator.
+pub(crate) fn write_sub_blocks(data: &[u8], out: &mut Vec<u8>) {
+    for block in data.chunks(255) {
+        out.push(block.len() as u8);
+        out.extend_from_slice(block);
+    }
+    out.push(0);
+}
@@ -0,0 +1,58 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Gif, GifSeeker};
+
+/// A decoded GIF, wrapped for use from JavaScript.
+///
+/// The `wasm/` directory holds the crate wasm-pack builds the package from:
+/// a `cdylib` that depends on this one with the `wasm` feature. This crate
+/// isn't a `cdylib` itself, since that would break `no_std` builds.
+#[wasm_bindgen]
+pub struct WasmGif {
+    gif: Gif,
+}
+
+#[wasm_bindgen]
+impl WasmGif {
+    /// Decodes a GIF held in memory, failing with the error's message.
+    pub fn from_bytes(data: &[u8]) -> Result<WasmGif, JsValue> {
+        Gif::from_bytes(data)
+            .map(|gif| WasmGif { gif })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Returns the canvas width in pixels.
+    pub fn width(&self) -> u32 {
+        self.gif.lsd.width as u32
+    }
+
+    /// Returns the canvas height in pixels.
+    pub fn height(&self) -> u32 {
+        self.gif.lsd.height as u32
+    }
+
+    /// Returns the number of frames.
+    pub fn frame_count(&self) -> u32 {
+        self.gif.frame_count() as u32
+    }
+
+    /// Returns how long the frame at `index` is displayed, in milliseconds,
+    /// as [`Gif::frame_delay_ms`] gives it, or `0` if there is no such frame.
+    pub fn frame_delay_ms(&self, index: u32) -> f64 {
+        self.gif.frame_delay_ms(index as usize).unwrap_or(0.0)
+    }
+
+    /// Returns the RGBA canvas as it appears while the frame at `index` is
+    /// displayed, as a `Uint8Array` of `width * height * 4` bytes.
+    ///
+    /// Every frame up to `index` is composited on each call. Fails with the
+    /// error's message if there is no such frame.
+    pub fn frame_rgba(&self, index: u32) -> Result<Vec<u8>, JsValue> {
+        GifSeeker::new(&self.gif)
+            .seek(index as usize)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "apng")]
+
+use std::io::Cursor;
+
+use gif::GifSeeker;
+
+mod common;
+use common::fixture;
+
+#[test]
+fn to_apng_bytes_writes_every_composited_frame() {
+    let gif = fixture("three-frames.gif");
+    let bytes = gif.to_apng_bytes().unwrap();
+
+    let mut reader = png::Decoder::new(Cursor::new(bytes)).read_info().unwrap();
+    let info = reader.info();
+    assert_eq!((info.width, info.height), (4, 4));
+    let animation = info.animation_control.unwrap();
+    assert_eq!(animation.num_frames, 3);
+    //the GIF repeats twice after its first play.
+    assert_eq!(animation.num_plays, 3);
+
+    let mut seeker = GifSeeker::new(&gif);
+    let mut buffer = vec![0; reader.output_buffer_size().unwrap()];
+    for (index, delay) in [10, 1, 25].iter().enumerate() {
+        let output = reader.next_frame(&mut buffer).unwrap();
+        assert_eq!((output.width, output.height), (4, 4));
+        let control = reader.info().frame_control.unwrap();
+        assert_eq!((control.delay_num, control.delay_den), (*delay, 100));
+        assert_eq!(
+            buffer[..output.buffer_size()],
+            seeker.seek(index).unwrap()[..]
+        );
+    }
+}
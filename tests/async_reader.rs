@@ -0,0 +1,28 @@
+#![cfg(feature = "async-tokio")]
+
+use gif::Gif;
+use tokio::fs::File;
+use tokio::io::BufReader;
+
+const EARTH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/earth.gif");
+
+#[tokio::test]
+async fn from_async_reader_matches_from_path() {
+    let mut reader = BufReader::new(File::open(EARTH).await.unwrap());
+    let gif = Gif::from_async_reader(&mut reader).await.unwrap();
+    let expected = Gif::from_path(EARTH).unwrap();
+    assert_eq!(gif.dimensions(), expected.dimensions());
+    assert_eq!(gif.frames.len(), expected.frames.len());
+    for (frame, expected) in gif.frames.iter().zip(&expected.frames) {
+        assert_eq!(frame.pixels, expected.pixels);
+    }
+}
+
+#[tokio::test]
+async fn from_async_reader_rejects_a_truncated_file() {
+    let bytes = std::fs::read(EARTH).unwrap();
+    let mut reader = BufReader::new(&bytes[..bytes.len() / 2]);
+    let expected = Gif::from_bytes(&bytes[..bytes.len() / 2]).unwrap_err();
+    let error = Gif::from_async_reader(&mut reader).await.unwrap_err();
+    assert_eq!(error.to_string(), expected.to_string());
+}
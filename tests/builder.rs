@@ -0,0 +1,33 @@
+//! Assembling GIFs from scratch.
+
+use gif::{Color, ColorTable, FrameBuilder, GifBuilder, GifError};
+
+#[test]
+fn a_built_gif_encodes_and_decodes() {
+    let gif = GifBuilder::new(2, 1)
+        .global_color_table(ColorTable::new(vec![Color::BLACK, Color::WHITE]))
+        .loop_count(0)
+        .add_frame(FrameBuilder::new(2, 1, vec![0, 1]).delay_centiseconds(5))
+        .build()
+        .unwrap();
+
+    let decoded = gif::Gif::from_bytes(&Vec::from(gif)).unwrap();
+    assert_eq!(decoded.dimensions(), (2, 1));
+    assert_eq!(decoded.loop_count, Some(0));
+    assert_eq!(decoded.frames[0].pixels, [0, 1]);
+}
+
+#[test]
+fn a_color_table_with_more_than_256_colors_is_rejected() {
+    let table = ColorTable::new(vec![Color::BLACK; 257]);
+    let result = GifBuilder::new(1, 1)
+        .global_color_table(table.clone())
+        .add_frame(FrameBuilder::new(1, 1, vec![0]))
+        .build();
+    assert!(matches!(result, Err(GifError::InvalidColorTable)));
+
+    let result = GifBuilder::new(1, 1)
+        .add_frame(FrameBuilder::new(1, 1, vec![0]).local_color_table(table))
+        .build();
+    assert!(matches!(result, Err(GifError::InvalidColorTable)));
+}
@@ -0,0 +1,111 @@
+//! Color arithmetic and color table helpers.
+
+use gif::{Color, ColorTable};
+
+mod common;
+use common::fixture;
+
+#[test]
+fn distance_sq_is_the_squared_euclidean_distance() {
+    assert_eq!(Color::BLACK.distance_sq(&Color::WHITE), 3 * 255 * 255);
+    assert_eq!(Color::RED.distance_sq(&Color::BLUE), 2 * 255 * 255);
+    assert_eq!(
+        Color::new(10, 20, 30).distance_sq(&Color::new(13, 16, 30)),
+        9 + 16
+    );
+    assert_eq!(Color::GREEN.distance_sq(&Color::GREEN), 0);
+}
+
+#[test]
+fn colors_are_ordered_by_luminance() {
+    //green carries most of the luma and blue the least.
+    assert!(Color::BLACK < Color::BLUE);
+    assert!(Color::BLUE < Color::RED);
+    assert!(Color::RED < Color::GREEN);
+    assert!(Color::GREEN < Color::WHITE);
+
+    let table = ColorTable::new(vec![Color::RED, Color::WHITE, Color::BLUE]);
+    assert_eq!(table.iter().max(), Some(&Color::WHITE));
+    assert_eq!(table.iter().min(), Some(&Color::BLUE));
+}
+
+#[test]
+fn colors_of_equal_luminance_are_ordered_by_channel() {
+    //both have a luma of 18.197.
+    let green = Color::new(0, 31, 0);
+    let blue = Color::new(1, 0, 157);
+    assert!(green < blue);
+    assert_eq!(green.cmp(&green), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn sorted_orders_a_table_from_darkest_to_brightest() {
+    let table = ColorTable::new(vec![Color::WHITE, Color::BLUE, Color::RED]);
+    assert_eq!(
+        table.sorted(),
+        ColorTable::new(vec![Color::BLUE, Color::RED, Color::WHITE])
+    );
+    //sorted leaves the original alone.
+    assert_eq!(table.get(0), Some(&Color::WHITE));
+}
+
+#[test]
+fn sort_by_luminance_keeps_equal_colors_in_order() {
+    let dark_green = Color::new(0, 31, 0);
+    let dark_blue = Color::new(1, 0, 157);
+    let mut table = ColorTable::new(vec![Color::WHITE, dark_blue, dark_green]);
+    table.sort_by_luminance();
+    //the two dark colors have the same luma, so they keep their order.
+    assert_eq!(
+        table,
+        ColorTable::new(vec![dark_blue, dark_green, Color::WHITE])
+    );
+}
+
+#[test]
+fn effective_color_table_prefers_the_local_table() {
+    let gif = fixture("local-palettes.gif");
+    let with_local = gif.frames[0].clone();
+    let mut without_local = with_local.clone();
+    without_local.local_color_table = None;
+    let local = with_local.local_color_table.as_ref();
+    let global = ColorTable::new(vec![Color::BLACK, Color::WHITE]);
+
+    assert_eq!(with_local.effective_color_table(Some(&global)), local);
+    assert_eq!(with_local.effective_color_table(None), local);
+    assert_eq!(
+        without_local.effective_color_table(Some(&global)),
+        Some(&global)
+    );
+    assert_eq!(without_local.effective_color_table(None), None);
+}
+
+#[test]
+fn blend_mixes_two_colors_by_alpha() {
+    assert_eq!(Color::RED.blend(Color::BLUE, 0.5), Color::new(128, 0, 128));
+    assert_eq!(Color::RED.blend(Color::BLUE, 1.0), Color::RED);
+    assert_eq!(Color::RED.blend(Color::BLUE, 0.0), Color::BLUE);
+    //alpha outside 0 to 1 is clamped per channel rather than wrapping.
+    assert_eq!(Color::WHITE.blend(Color::BLACK, 2.0), Color::WHITE);
+    assert_eq!(Color::WHITE.blend(Color::BLACK, -1.0), Color::BLACK);
+}
+
+#[test]
+fn blend_rgba_composites_source_over() {
+    let opaque_blue = [0, 0, 255, 255];
+    assert_eq!(
+        Color::blend_rgba([255, 0, 0, 255], opaque_blue),
+        [255, 0, 0, 255]
+    );
+    assert_eq!(Color::blend_rgba([255, 0, 0, 0], opaque_blue), opaque_blue);
+    assert_eq!(
+        Color::blend_rgba([255, 0, 0, 128], opaque_blue),
+        [128, 0, 127, 255]
+    );
+    //half over half covers three quarters.
+    assert_eq!(
+        Color::blend_rgba([255, 0, 0, 128], [0, 0, 255, 128]),
+        [170, 0, 85, 192]
+    );
+    assert_eq!(Color::blend_rgba([9, 9, 9, 0], [7, 7, 7, 0]), [0, 0, 0, 0]);
+}
@@ -0,0 +1,14 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use gif::Gif;
+
+/// Returns the path of `name` in `tests/fixtures`.
+pub fn path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+/// Decodes the fixture called `name`.
+pub fn fixture(name: &str) -> Gif {
+    Gif::from_path(path(name)).unwrap()
+}
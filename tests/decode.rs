@@ -0,0 +1,340 @@
+//! Decoding the files in `tests/fixtures`.
+
+use std::fs::File;
+use std::io::Cursor;
+
+use gif::{Color, ColorTable, DisposalMethod, Gif, GifDecoder, GifEvent, GifVersion};
+
+mod common;
+use common::{fixture, path};
+
+#[test]
+fn decodes_the_header_and_screen_descriptor() {
+    let gif = fixture("sample.gif");
+    assert!(matches!(gif.version, GifVersion::V89a));
+    assert_eq!((gif.lsd.width, gif.lsd.height), (4, 2));
+    assert!(gif.lsd.has_global_color_table);
+    assert_eq!(gif.global_color_table.unwrap().len(), 4);
+}
+
+#[test]
+fn rejects_data_that_is_not_a_gif() {
+    assert!(Gif::from_bytes(b"PNG\r\n\x1a\n").is_err());
+}
+
+#[test]
+fn reads_the_canvas_size_little_endian() {
+    //640 and 480 have both bytes set, so swapped or mis-shifted bytes show up.
+    let gif = fixture("640x480.gif");
+    assert_eq!((gif.lsd.width, gif.lsd.height), (640, 480));
+    assert_eq!(gif.frames[0].pixels.len(), 640 * 480);
+}
+
+#[test]
+fn reads_global_color_tables_of_every_size() {
+    for flag in 0..8u8 {
+        let colors = 2usize << flag;
+        let mut bytes = b"GIF89a\x01\x00\x01\x00".to_vec();
+        bytes.extend_from_slice(&[0b1000_0000 | flag, 0, 0]);
+        bytes.extend((0..colors * 3).map(|i| i as u8));
+        bytes.push(0x3B);
+
+        let gif = Gif::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            gif.global_color_table.unwrap().len(),
+            colors,
+            "size flag {}",
+            flag
+        );
+    }
+}
+
+#[test]
+fn from_reader_reads_a_cursor() {
+    let bytes = std::fs::read(path("sample.gif")).unwrap();
+    let gif = Gif::from_reader(&mut Cursor::new(&bytes[..])).unwrap();
+    assert_eq!((gif.lsd.width, gif.lsd.height), (4, 2));
+    assert_eq!(gif.frames[0].pixels, [0, 1, 2, 0, 1, 2, 0, 1]);
+}
+
+#[test]
+fn from_reader_reads_a_file() {
+    let mut file = File::open(path("sample.gif")).unwrap();
+    let gif = Gif::from_reader(&mut file).unwrap();
+    assert_eq!((gif.lsd.width, gif.lsd.height), (4, 2));
+    assert_eq!(gif.frames[0].pixels, [0, 1, 2, 0, 1, 2, 0, 1]);
+}
+
+#[test]
+fn from_reader_ignores_data_after_the_trailer() {
+    let mut bytes = std::fs::read(path("sample.gif")).unwrap();
+    bytes.extend_from_slice(b"trailing data");
+    let mut cursor = Cursor::new(&bytes[..]);
+    assert_eq!(Gif::from_reader(&mut cursor).unwrap().frames.len(), 2);
+}
+
+#[test]
+fn parse_header_reads_only_the_header() {
+    let bytes = std::fs::read(path("sample.gif")).unwrap();
+    let mut reader = &bytes[..];
+    assert!(matches!(
+        gif::parse_header(&mut reader).unwrap(),
+        GifVersion::V89a
+    ));
+    assert_eq!(reader.len(), bytes.len() - 6);
+}
+
+#[test]
+fn peek_dimensions_reads_only_the_header_and_screen_descriptor() {
+    let bytes = std::fs::read(path("640x480.gif")).unwrap();
+    let mut reader = &bytes[..];
+    assert_eq!(Gif::peek_dimensions(&mut reader).unwrap(), (640, 480));
+    assert_eq!(reader.len(), bytes.len() - 13);
+}
+
+#[test]
+fn collects_every_image_descriptor() {
+    let gif = fixture("multi-image.gif");
+    let positions: Vec<_> = gif
+        .image_descriptors
+        .iter()
+        .map(|d| (d.left, d.top, d.width, d.height))
+        .collect();
+    assert_eq!(positions, [(0, 0, 2, 1), (2, 0, 2, 1)]);
+    for descriptor in &gif.image_descriptors {
+        assert!(!descriptor.has_local_color_table);
+        assert!(!descriptor.is_interlaced);
+        assert!(!descriptor.is_sorted);
+    }
+    assert_eq!(gif.frames[1].pixels, [1, 0]);
+}
+
+#[test]
+fn attaches_graphic_control_extensions_to_their_frames() {
+    let gif = fixture("sample.gif");
+    let first = gif.frames[0].graphic_control.as_ref().unwrap();
+    assert_eq!(first.delay_centiseconds, 10);
+    assert!(matches!(
+        first.disposal_method,
+        DisposalMethod::Unspecified(0)
+    ));
+    assert_eq!(first.transparent_color_index, None);
+    assert!(!first.user_input);
+
+    let second = gif.frames[1].graphic_control.as_ref().unwrap();
+    assert_eq!(second.delay_centiseconds, 0);
+    assert!(matches!(
+        second.disposal_method,
+        DisposalMethod::DoNotDispose
+    ));
+    assert_eq!(second.transparent_color_index, Some(0));
+}
+
+#[test]
+fn reads_known_delays_from_an_animation() {
+    let gif = fixture("three-frames.gif");
+    let delays: Vec<_> = gif
+        .frames
+        .iter()
+        .map(|frame| frame.graphic_control.as_ref().unwrap().delay_centiseconds)
+        .collect();
+    assert_eq!(delays, [10, 1, 25]);
+}
+
+#[test]
+fn reads_an_infinite_loop_count() {
+    assert_eq!(fixture("sample.gif").loop_count, Some(0));
+}
+
+#[test]
+fn reads_a_finite_loop_count() {
+    assert_eq!(fixture("three-frames.gif").loop_count, Some(2));
+}
+
+#[test]
+fn a_gif_without_a_netscape_extension_plays_once() {
+    assert_eq!(fixture("multi-image.gif").loop_count, None);
+}
+
+#[test]
+fn collects_each_comment_extension() {
+    let gif = fixture("comments.gif");
+    assert_eq!(gif.comments, ["hello", "wor\u{FFFD}ld"]);
+}
+
+#[test]
+fn joins_the_sub_blocks_of_a_comment() {
+    let mut bytes = b"GIF89a\x01\x00\x01\x00\x00\x00\x00".to_vec();
+    bytes.extend_from_slice(b"\x21\xFE\x03abc\x02de\x00\x3B");
+    assert_eq!(Gif::from_bytes(&bytes).unwrap().comments, ["abcde"]);
+}
+
+#[test]
+fn keeps_the_data_of_unknown_extensions() {
+    let gif = fixture("unknown-extension.gif");
+    assert_eq!(gif.unknown_extensions, [(0x99, b"abcde".to_vec())]);
+    assert_eq!(gif.frames.len(), 1);
+
+    let reencoded = Gif::from_bytes(&Vec::from(gif)).unwrap();
+    assert_eq!(reencoded.unknown_extensions, [(0x99, b"abcde".to_vec())]);
+}
+
+#[test]
+fn gives_each_frame_its_local_color_table() {
+    let gif = fixture("local-palettes.gif");
+    assert!(gif.global_color_table.is_none());
+
+    let red_green = ColorTable::new(vec![Color::RED, Color::GREEN]);
+    let blue_white = ColorTable::new(vec![Color::BLUE, Color::WHITE]);
+    assert_eq!(gif.frames[0].local_color_table.as_ref(), Some(&red_green));
+    assert_eq!(gif.frames[1].local_color_table.as_ref(), Some(&blue_white));
+    assert_eq!(gif.frame_color_table(1), Some(&blue_white));
+
+    //both frames use the same indices, which only mean different colors through their tables.
+    assert_eq!(gif.pixel_at(0, 1, 0).unwrap(), Color::GREEN);
+    assert_eq!(gif.pixel_at(1, 1, 0).unwrap(), Color::WHITE);
+}
+
+#[test]
+fn deinterlaces_interlaced_images() {
+    let interlaced = fixture("interlaced.gif");
+    let progressive = fixture("progressive.gif");
+    assert!(interlaced.frames[0].descriptor.is_interlaced);
+    assert!(!progressive.frames[0].descriptor.is_interlaced);
+    assert_eq!(interlaced.frames[0].pixels, progressive.frames[0].pixels);
+
+    //row y of both images holds (x + y) % 4 at column x.
+    let rows: Vec<_> = progressive.frames[0].rows().map(|row| row[0]).collect();
+    assert_eq!(rows, [0, 1, 2, 3, 0, 1, 2, 3, 0, 1]);
+}
+
+#[test]
+fn an_animation_has_more_than_one_frame() {
+    let gif = fixture("three-frames.gif");
+    assert_eq!(gif.frame_count(), 3);
+    assert!(gif.is_animated());
+    assert_eq!(gif.dimensions(), (4, 4));
+}
+
+#[test]
+fn a_still_image_has_one_frame() {
+    let gif = fixture("640x480.gif");
+    assert_eq!(gif.frame_count(), 1);
+    assert!(!gif.is_animated());
+    assert_eq!(gif.dimensions(), (640, 480));
+}
+
+#[test]
+fn displays_the_version_as_in_the_header() {
+    assert_eq!(format!("{}", GifVersion::V89a), "GIF89a");
+    assert_eq!(format!("{}", GifVersion::V87a), "GIF87a");
+}
+
+#[test]
+fn displays_a_summary_of_the_gif() {
+    assert_eq!(
+        fixture("sample.gif").to_string(),
+        "GIF89a 4x2, 2 frames, global color table of 4 colors"
+    );
+    assert_eq!(
+        fixture("local-palettes.gif").to_string(),
+        "GIF89a 2x1, 2 frames, no global color table"
+    );
+}
+
+#[test]
+fn pads_image_data_that_ends_early() {
+    //a 4x2 image whose data only holds two pixels.
+    let mut bytes = b"GIF89a\x04\x00\x02\x00\x80\x00\x00\x00\x00\x00\xFF\xFF\xFF".to_vec();
+    bytes.extend_from_slice(b"\x2C\x00\x00\x00\x00\x04\x00\x02\x00\x00\x02");
+    bytes.extend(gif::lzw::encode(2, &[1, 1]));
+    bytes.push(0x3B);
+
+    let gif = Gif::from_bytes(&bytes).unwrap();
+    assert_eq!(gif.frames[0].pixels, [1, 1, 0, 0, 0, 0, 0, 0]);
+    //so it can be encoded again without tripping over the short frame.
+    let reencoded = Gif::from_bytes(&Vec::from(gif)).unwrap();
+    assert_eq!(reencoded.frames[0].pixels, [1, 1, 0, 0, 0, 0, 0, 0]);
+}
+
+/// A 2x2 GIF whose image data decompresses to far more than four pixels.
+fn overlong_image() -> Vec<u8> {
+    let mut bytes = b"GIF89a\x02\x00\x02\x00\x80\x00\x00\x00\x00\x00\xFF\xFF\xFF".to_vec();
+    bytes.extend_from_slice(b"\x2C\x00\x00\x00\x00\x02\x00\x02\x00\x00\x02");
+    bytes.extend(gif::lzw::encode(2, &[1; 10_000]));
+    bytes.push(0x3B);
+    bytes
+}
+
+#[test]
+fn stops_decompressing_at_the_image_size() {
+    let gif = Gif::from_bytes(&overlong_image()).unwrap();
+    assert_eq!(gif.frames[0].pixels, [1, 1, 1, 1]);
+}
+
+#[test]
+fn gif_decoder_stops_decompressing_at_the_image_size() {
+    let bytes = overlong_image();
+    let mut decoder = GifDecoder::new(&bytes[..]);
+    let mut frames = vec![];
+    while let Some(event) = decoder.next_event().unwrap() {
+        if let GifEvent::FramePixels(pixels) = event {
+            frames.push(pixels);
+        }
+    }
+    assert_eq!(frames, [[1, 1, 1, 1]]);
+}
+
+#[test]
+fn lzw_decode_stops_at_the_pixel_count() {
+    let encoded = gif::lzw::encode(2, &[3; 1000]);
+    assert_eq!(gif::lzw::decode(2, &encoded, 5).unwrap(), [3; 5]);
+    assert_eq!(gif::lzw::decode(2, &encoded, 0).unwrap(), []);
+}
+
+#[test]
+fn images_without_graphic_control_show_together() {
+    let gif = fixture("multi-image.gif");
+    assert_eq!(gif.frame_count(), 2);
+    for frame in &gif.frames {
+        assert!(frame.graphic_control.is_none());
+        assert_eq!(frame.delay_centiseconds(), 0);
+        assert!(matches!(
+            frame.disposal_method(),
+            DisposalMethod::DoNotDispose
+        ));
+    }
+
+    //the second image is drawn beside the first, which stays on the canvas.
+    let row: Vec<_> = (0..4).map(|x| gif.pixel_at(1, x, 0).unwrap()).collect();
+    assert_eq!(
+        row,
+        [Color::WHITE, Color::WHITE, Color::WHITE, Color::BLACK]
+    );
+}
+
+#[test]
+fn decodes_a_gif87a_still_image() {
+    let gif = fixture("still-87a.gif");
+    assert!(matches!(gif.version, GifVersion::V87a));
+    assert_eq!(gif.dimensions(), (3, 2));
+    assert_eq!(gif.frame_count(), 1);
+    assert!(gif.frames[0].graphic_control.is_none());
+    assert_eq!(gif.frames[0].pixels, [0, 1, 2, 3, 2, 1]);
+
+    let colors: Vec<_> = (0..2)
+        .flat_map(|y| (0..3).map(move |x| (x, y)))
+        .map(|(x, y)| gif.pixel_at(0, x, y).unwrap())
+        .collect();
+    assert_eq!(
+        colors,
+        [
+            Color::RED,
+            Color::GREEN,
+            Color::BLUE,
+            Color::WHITE,
+            Color::BLUE,
+            Color::GREEN
+        ]
+    );
+}
@@ -0,0 +1,188 @@
+//! Operations that build a new GIF out of an existing one.
+
+use gif::{Color, ColorTable, FrameBuilder, Gif, GifBuilder, GifError, GifSeeker};
+
+mod common;
+use common::fixture;
+
+/// A still 100x100 GIF split into red, green, blue and white quadrants.
+fn quadrants() -> Gif {
+    let pixels = (0..100)
+        .flat_map(|y| (0..100).map(move |x| (x / 50 + y / 50 * 2) as u8))
+        .collect();
+    GifBuilder::new(100, 100)
+        .global_color_table(ColorTable::new(vec![
+            Color::RED,
+            Color::GREEN,
+            Color::BLUE,
+            Color::WHITE,
+        ]))
+        .add_frame(FrameBuilder::new(100, 100, pixels))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn scale_halves_a_100x100_gif() {
+    let gif = quadrants();
+    let scaled = gif.scale(50, 50).unwrap();
+    assert_eq!(scaled.dimensions(), (50, 50));
+    assert_eq!(scaled.frames[0].pixels.len(), 50 * 50);
+    assert_eq!(scaled.global_color_table, gif.global_color_table);
+
+    assert_eq!(scaled.pixel_at(0, 0, 0).unwrap(), Color::RED);
+    assert_eq!(scaled.pixel_at(0, 49, 0).unwrap(), Color::GREEN);
+    assert_eq!(scaled.pixel_at(0, 0, 49).unwrap(), Color::BLUE);
+    assert_eq!(scaled.pixel_at(0, 49, 49).unwrap(), Color::WHITE);
+    //the quadrant edges land exactly halfway.
+    assert_eq!(scaled.pixel_at(0, 24, 24).unwrap(), Color::RED);
+    assert_eq!(scaled.pixel_at(0, 25, 25).unwrap(), Color::WHITE);
+}
+
+#[test]
+fn scale_rejects_an_empty_size() {
+    let gif = quadrants();
+    assert!(matches!(gif.scale(0, 50), Err(GifError::InvalidDimensions)));
+    assert!(matches!(gif.scale(50, 0), Err(GifError::InvalidDimensions)));
+}
+
+#[test]
+fn reverse_frames_plays_the_composited_frames_backwards() {
+    let gif = fixture("three-frames.gif");
+    let reversed = gif.reverse_frames();
+    assert_eq!(reversed.frame_count(), 3);
+    assert_eq!(reversed.loop_count, gif.loop_count);
+
+    let delays: Vec<_> = reversed
+        .frames
+        .iter()
+        .map(|f| f.delay_centiseconds())
+        .collect();
+    assert_eq!(delays, [25, 1, 10]);
+
+    let mut original = GifSeeker::new(&gif);
+    let mut backwards = GifSeeker::new(&reversed);
+    for index in 0..3 {
+        assert!(
+            backwards.seek(index).unwrap() == original.seek(2 - index).unwrap(),
+            "frame {}",
+            index
+        );
+    }
+}
+
+#[test]
+fn reverse_frames_keeps_partial_frames_composited() {
+    //the second frame of sample.gif only patches a 2x1 rectangle.
+    let gif = fixture("sample.gif");
+    let reversed = gif.reverse_frames();
+
+    let mut original = GifSeeker::new(&gif);
+    let mut backwards = GifSeeker::new(&reversed);
+    assert!(backwards.seek(0).unwrap() == original.seek(1).unwrap());
+    assert!(backwards.seek(1).unwrap() == original.seek(0).unwrap());
+}
+
+#[test]
+fn removing_the_only_frame_leaves_no_frames() {
+    let mut gif = fixture("still-87a.gif");
+    let frame = gif.remove_frame(0).unwrap();
+    assert_eq!(frame.pixels, [0, 1, 2, 3, 2, 1]);
+    assert_eq!(gif.frame_count(), 0);
+    assert!(!gif.is_animated());
+    assert!(gif.image_descriptors.is_empty());
+}
+
+#[test]
+fn remove_frame_fails_past_the_last_frame() {
+    let mut gif = fixture("three-frames.gif");
+    assert!(matches!(gif.remove_frame(3), Err(GifError::OutOfBounds)));
+    assert_eq!(gif.frame_count(), 3);
+
+    gif.remove_frame(1).unwrap();
+    assert_eq!(gif.frame_count(), 2);
+    let delays: Vec<_> = gif.frames.iter().map(|f| f.delay_centiseconds()).collect();
+    assert_eq!(delays, [10, 25]);
+}
+
+#[test]
+fn splice_frames_replaces_a_range() {
+    let mut gif = fixture("three-frames.gif");
+    let replacement = vec![gif.frames[2].clone(), gif.frames[2].clone()];
+    let removed = gif.splice_frames(0..2, replacement).unwrap();
+
+    let delays = |frames: &[gif::Frame]| -> Vec<u16> {
+        frames.iter().map(|f| f.delay_centiseconds()).collect()
+    };
+    assert_eq!(delays(&removed), [10, 1]);
+    assert_eq!(delays(&gif.frames), [25, 25, 25]);
+    assert_eq!(gif.image_descriptors.len(), 3);
+
+    assert!(matches!(
+        gif.splice_frames(2..4, vec![]),
+        Err(GifError::OutOfBounds)
+    ));
+    assert_eq!(gif.frame_count(), 3);
+}
+
+/// A still 2x1 GIF of `pixels` over a global color table of `table`.
+fn still(table: Vec<Color>, pixels: Vec<u8>) -> Gif {
+    GifBuilder::new(2, 1)
+        .global_color_table(ColorTable::new(table))
+        .add_frame(FrameBuilder::new(2, 1, pixels))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn concat_merges_global_color_tables_that_fit() {
+    let mut first = still(vec![Color::BLACK, Color::WHITE], vec![0, 1]);
+    first.loop_count = Some(0);
+    let second = still(vec![Color::WHITE, Color::RED], vec![1, 0]);
+
+    let joined = first.concat(&second).unwrap();
+    assert!(joined.validate().is_ok());
+    assert_eq!(joined.loop_count, Some(0));
+    assert_eq!(
+        joined.global_color_table,
+        Some(ColorTable::new(vec![
+            Color::BLACK,
+            Color::WHITE,
+            Color::RED
+        ]))
+    );
+    assert!(joined.frames[1].local_color_table.is_none());
+    assert_eq!(joined.pixel_at(0, 0, 0).unwrap(), Color::BLACK);
+    assert_eq!(joined.pixel_at(1, 0, 0).unwrap(), Color::RED);
+    assert_eq!(joined.pixel_at(1, 1, 0).unwrap(), Color::WHITE);
+}
+
+#[test]
+fn concat_gives_frames_a_local_table_when_the_merge_overflows() {
+    let first = still(vec![Color::BLACK, Color::WHITE], vec![0, 1]);
+    let many: Vec<_> = (0..=255).map(|i| Color::new(i, 0, 1)).collect();
+    let second = still(many.clone(), vec![7, 200]);
+
+    let joined = first.concat(&second).unwrap();
+    assert!(joined.validate().is_ok());
+    assert_eq!(joined.global_color_table, first.global_color_table);
+    assert_eq!(
+        joined.frames[1].local_color_table,
+        Some(ColorTable::new(many))
+    );
+    assert_eq!(joined.pixel_at(1, 0, 0).unwrap(), Color::new(7, 0, 1));
+    assert_eq!(joined.pixel_at(1, 1, 0).unwrap(), Color::new(200, 0, 1));
+}
+
+#[test]
+fn concat_rejects_a_canvas_of_another_size() {
+    let sample = fixture("sample.gif");
+    let square = fixture("three-frames.gif");
+    assert!(matches!(
+        sample.concat(&square),
+        Err(GifError::DimensionMismatch {
+            expected: (4, 2),
+            actual: (4, 4),
+        })
+    ));
+}
@@ -0,0 +1,199 @@
+//! Encoding GIFs and decoding the result again.
+
+use gif::{Gif, GifEncoder};
+
+mod common;
+use common::{fixture, path};
+
+const EARTH: &[u8] = include_bytes!("../earth.gif");
+
+/// Checks that `decoded` holds the same frames as `original`.
+fn assert_same_frames(original: &Gif, decoded: &Gif) {
+    assert_eq!(decoded.dimensions(), original.dimensions());
+    assert_eq!(decoded.frame_count(), original.frame_count());
+    for (index, (a, b)) in original.frames.iter().zip(&decoded.frames).enumerate() {
+        let position = |frame: &gif::Frame| {
+            let d = &frame.descriptor;
+            (d.left, d.top, d.width, d.height)
+        };
+        assert_eq!(position(a), position(b), "frame {}", index);
+        assert_eq!(a.pixels, b.pixels, "frame {}", index);
+        assert_eq!(
+            a.delay_centiseconds(),
+            b.delay_centiseconds(),
+            "frame {}",
+            index
+        );
+        assert_eq!(
+            original.frame_color_table(index),
+            decoded.frame_color_table(index),
+            "frame {}",
+            index
+        );
+    }
+}
+
+#[test]
+fn gif_encoder_output_decodes_to_the_same_pixels() {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    let (width, height) = gif.dimensions();
+    let mut encoder = GifEncoder::new(vec![], width, height, gif.global_color_table.as_ref());
+    for frame in &gif.frames {
+        encoder.write_frame(frame).unwrap();
+    }
+    let bytes = encoder.finish().unwrap();
+
+    assert_same_frames(&gif, &Gif::from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn gif_encoder_writes_local_color_tables() {
+    let gif = fixture("local-palettes.gif");
+    let mut encoder = GifEncoder::new(vec![], 2, 1, None);
+    for frame in &gif.frames {
+        encoder.write_frame(frame).unwrap();
+    }
+    let decoded = Gif::from_bytes(&encoder.finish().unwrap()).unwrap();
+
+    assert!(decoded.global_color_table.is_none());
+    assert_same_frames(&gif, &decoded);
+}
+
+/// A path in the system temp directory that no other test uses.
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("gif-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn save_writes_a_file_that_loads_back() {
+    let gif = fixture("three-frames.gif");
+    let path = temp_path("save.gif");
+    gif.save(&path).unwrap();
+    let saved = Gif::from_path(&path);
+    std::fs::remove_file(&path).unwrap();
+    let saved = saved.unwrap();
+
+    assert_eq!(saved.frame_count(), gif.frame_count());
+    assert_eq!(saved.dimensions(), gif.dimensions());
+    assert_same_frames(&gif, &saved);
+}
+
+#[test]
+fn save_keeps_the_loop_count_and_comments() {
+    let mut gif = fixture("sample.gif");
+    gif.comments.push(String::from("saved by a test"));
+    let path = temp_path("extensions.gif");
+    gif.save(&path).unwrap();
+    let saved = Gif::from_path(&path);
+    std::fs::remove_file(&path).unwrap();
+    let saved = saved.unwrap();
+
+    assert_eq!(saved.loop_count, Some(0));
+    assert_eq!(saved.comments, ["saved by a test"]);
+    let graphic_control = saved.frames[1].graphic_control.as_ref().unwrap();
+    assert_eq!(graphic_control.transparent_color_index, Some(0));
+}
+
+#[test]
+fn as_still_image_encodes_as_a_single_frame_gif() {
+    let gif = fixture("sample.gif");
+    let still = gif.as_still_image().unwrap();
+    assert_eq!(still.frame_count(), 1);
+    assert_eq!(still.loop_count, None);
+    assert_eq!(still.image_descriptors.len(), 1);
+
+    let bytes = Vec::from(still);
+    assert!(!bytes.windows(11).any(|window| window == b"NETSCAPE2.0"));
+    let decoded = Gif::from_bytes(&bytes).unwrap();
+    assert!(!decoded.is_animated());
+    assert_eq!(decoded.loop_count, None);
+    assert_eq!(decoded.dimensions(), gif.dimensions());
+    assert_eq!(decoded.global_color_table, gif.global_color_table);
+    assert_eq!(decoded.frames[0].pixels, gif.frames[0].pixels);
+}
+
+#[test]
+fn as_still_image_needs_a_frame() {
+    let mut gif = fixture("sample.gif");
+    gif.remove_frame(1).unwrap();
+    gif.remove_frame(0).unwrap();
+    assert!(gif.as_still_image().is_none());
+}
+
+/// Returns the label and raw bytes of every extension block in `bytes`.
+fn extensions(bytes: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    gif::GifParser::parse_blocks(&mut &bytes[..])
+        .unwrap()
+        .into_iter()
+        .filter_map(|block| match block {
+            gif::Block::Extension(extension) => {
+                Some((extension.label(), extension.raw_bytes().to_vec()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn extension_raw_bytes_survive_parsing_and_encoding() {
+    let bytes = std::fs::read(path("unknown-extension.gif")).unwrap();
+    let parsed = extensions(&bytes);
+    assert_eq!(parsed, [(0x99, b"abcde".to_vec())]);
+
+    //write the raw bytes back out through the encoder and parse them again.
+    let gif = Gif::from_bytes(&bytes).unwrap();
+    let mut encoder = GifEncoder::new(vec![], 1, 1, gif.global_color_table.as_ref());
+    for (label, raw_bytes) in &parsed {
+        encoder.write_extension(*label, raw_bytes).unwrap();
+    }
+    encoder.write_frame(&gif.frames[0]).unwrap();
+    assert_eq!(extensions(&encoder.finish().unwrap()), parsed);
+}
+
+#[test]
+fn extension_raw_bytes_of_known_extensions() {
+    let bytes = Vec::from(fixture("sample.gif"));
+    let parsed = extensions(&bytes);
+    let labels: Vec<_> = parsed.iter().map(|(label, _)| *label).collect();
+    assert_eq!(labels, [0xFF, 0xF9, 0xF9]);
+    //the NETSCAPE2.0 identifier, then the loop count sub-block.
+    assert_eq!(parsed[0].1, b"NETSCAPE2.0\x01\x00\x00");
+    //packed fields, a delay of 10 cs, and no transparent index.
+    assert_eq!(parsed[1].1, [0, 10, 0, 0]);
+}
+
+#[test]
+fn write_to_streams_a_gif_that_decodes() {
+    let gif = fixture("three-frames.gif");
+    let mut output = vec![];
+    let written = gif.write_to(&mut output).unwrap();
+    assert_eq!(written, output.len());
+    assert_eq!(output, Vec::from(gif.clone()));
+    assert_same_frames(&gif, &Gif::from_bytes(&output).unwrap());
+}
+
+#[test]
+fn write_to_flushes_the_writer() {
+    /// Buffers everything and only counts as written once flushed.
+    #[derive(Default)]
+    struct Buffered {
+        pending: Vec<u8>,
+        flushed: Vec<u8>,
+    }
+    impl std::io::Write for Buffered {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.pending.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushed.append(&mut self.pending);
+            Ok(())
+        }
+    }
+
+    let gif = fixture("sample.gif");
+    let mut writer = Buffered::default();
+    let written = gif.write_to(&mut writer).unwrap();
+    assert!(writer.pending.is_empty());
+    assert_eq!(writer.flushed.len(), written);
+}
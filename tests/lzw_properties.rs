@@ -0,0 +1,87 @@
+//! Round-trip properties of the LZW codec across every minimum code size a
+//! GIF can use for 2 to 256 colors.
+
+//proptest is only a dev-dependency off wasm32.
+#![cfg(not(target_arch = "wasm32"))]
+
+use gif::lzw::{decode, encode};
+use proptest::prelude::*;
+
+/// Pixel data for a random minimum code size, with every index inside the
+/// range that code size can represent.
+fn pixels() -> impl Strategy<Value = (u8, Vec<u8>)> {
+    (2u8..=8, prop::collection::vec(any::<u8>(), 0..10_000)).prop_map(|(min_code_size, pixels)| {
+        let mask = ((1u16 << min_code_size) - 1) as u8;
+        let pixels = pixels.into_iter().map(|pixel| pixel & mask).collect();
+        (min_code_size, pixels)
+    })
+}
+
+/// Long runs drawn from a few indices, so the strings in the code table grow
+/// long. Random data at the larger code sizes is what fills the table up and
+/// forces a clear partway through.
+fn runs() -> impl Strategy<Value = (u8, Vec<u8>)> {
+    (
+        2u8..=8,
+        prop::collection::vec((0u8..4, 1usize..300), 0..200),
+    )
+        .prop_map(|(min_code_size, runs)| {
+            let pixels = runs
+                .into_iter()
+                .flat_map(|(index, len)| std::iter::repeat_n(index, len))
+                .collect();
+            (min_code_size, pixels)
+        })
+}
+
+/// Reads the first `count` codes of `width` bits from sub-block data.
+fn leading_codes(sub_blocks: &[u8], width: u8, count: usize) -> Vec<u16> {
+    let data = gif::SubBlockReader(sub_blocks).data();
+    (0..count)
+        .map(|n| {
+            (0..width as usize).fold(0u16, |code, bit| {
+                let position = n * width as usize + bit;
+                let set = data[position / 8] >> (position % 8) & 1;
+                code | (set as u16) << bit
+            })
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn decode_inverts_encode((min_code_size, pixels) in pixels()) {
+        let encoded = encode(min_code_size, &pixels);
+        prop_assert_eq!(decode(min_code_size, &encoded, pixels.len()).unwrap(), pixels);
+    }
+
+    #[test]
+    fn decode_inverts_encode_for_long_runs((min_code_size, pixels) in runs()) {
+        let encoded = encode(min_code_size, &pixels);
+        prop_assert_eq!(decode(min_code_size, &encoded, pixels.len()).unwrap(), pixels);
+    }
+
+    #[test]
+    fn decoding_stops_at_the_end_of_information_code((min_code_size, pixels) in pixels()) {
+        let encoded = encode(min_code_size, &pixels);
+        prop_assert_eq!(decode(min_code_size, &encoded, usize::MAX).unwrap(), pixels);
+    }
+}
+
+#[test]
+fn encoding_nothing_writes_a_clear_and_an_end_of_information_code() {
+    for min_code_size in 2..=8 {
+        let encoded = encode(min_code_size, &[]);
+        assert_eq!(encoded.last(), Some(&0), "missing block terminator");
+
+        let clear_code = 1 << min_code_size;
+        assert_eq!(
+            leading_codes(&encoded, min_code_size + 1, 2),
+            vec![clear_code, clear_code + 1]
+        );
+        assert_eq!(
+            decode(min_code_size, &encoded, usize::MAX).unwrap(),
+            Vec::<u8>::new()
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Shrinking animations to the regions that change.
+
+use gif::{Color, ColorTable, FrameBuilder, Gif, GifBuilder, GifOptimizer, GifSeeker};
+
+mod common;
+use common::fixture;
+
+const EARTH: &[u8] = include_bytes!("../earth.gif");
+
+/// Decodes the encoded optimized GIF and checks every composited frame
+/// against the original.
+fn assert_renders_the_same(original: &Gif, optimized: &Gif) {
+    let optimized = Gif::from_bytes(&Vec::from(optimized.clone())).unwrap();
+    assert_eq!(optimized.frame_count(), original.frame_count());
+    let mut expected = GifSeeker::new(original);
+    let mut actual = GifSeeker::new(&optimized);
+    for index in 0..original.frame_count() {
+        assert!(
+            expected.seek(index).unwrap() == actual.seek(index).unwrap(),
+            "frame {} differs",
+            index
+        );
+    }
+}
+
+#[test]
+fn optimized_frames_cover_only_what_changed() {
+    let gif = fixture("three-frames.gif");
+    let optimized = GifOptimizer::optimize(&gif);
+    assert_renders_the_same(&gif, &optimized);
+
+    let rectangles: Vec<_> = optimized
+        .frames
+        .iter()
+        .map(|frame| {
+            let d = &frame.descriptor;
+            (d.left, d.top, d.width, d.height)
+        })
+        .collect();
+    assert_eq!(rectangles, [(0, 0, 4, 4), (1, 1, 1, 1), (2, 2, 1, 1)]);
+}
+
+#[test]
+fn an_optimized_animation_renders_the_same_and_is_smaller() {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    let optimized = GifOptimizer::optimize(&gif);
+    assert_renders_the_same(&gif, &optimized);
+    assert!(Vec::from(optimized).len() <= Vec::from(gif).len());
+}
+
+/// A 16x16 animation over a full four-color table with no transparent
+/// index, whose first frame is noise in the first three colors and whose
+/// second changes only the corners to `corner`.
+fn full_palette_animation(corner: u8) -> Gif {
+    let noise: Vec<u8> = (0..16 * 16).map(|i| (i * 7 % 11 % 3) as u8).collect();
+    let mut changed = noise.clone();
+    changed[0] = (noise[0] + 1) % 3;
+    changed[16 * 16 - 1] = corner;
+    GifBuilder::new(16, 16)
+        .global_color_table(ColorTable::new(vec![
+            Color::RED,
+            Color::GREEN,
+            Color::BLUE,
+            Color::WHITE,
+        ]))
+        .add_frame(FrameBuilder::new(16, 16, noise))
+        .add_frame(FrameBuilder::new(16, 16, changed))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn deltas_borrow_an_unused_index_as_transparent() {
+    let gif = full_palette_animation(1);
+    let optimized = GifOptimizer::optimize(&gif);
+    assert!(optimized.validate().is_ok());
+    assert_renders_the_same(&gif, &optimized);
+    let graphic_control = optimized.frames[1].graphic_control.as_ref().unwrap();
+    assert_eq!(graphic_control.transparent_color_index, Some(3));
+}
+
+#[test]
+fn deltas_that_use_every_index_have_no_transparency() {
+    let gif = full_palette_animation(3);
+    let optimized = GifOptimizer::optimize(&gif);
+    assert!(optimized.validate().is_ok());
+    assert_renders_the_same(&gif, &optimized);
+    let graphic_control = optimized.frames[1].graphic_control.as_ref().unwrap();
+    assert_eq!(graphic_control.transparent_color_index, None);
+}
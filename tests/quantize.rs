@@ -0,0 +1,56 @@
+//! Reducing true-color images to a palette.
+
+use gif::quantize::quantize;
+
+/// A 16 by 16 RGBA image: red rises to the right, green downwards, and
+/// blue is constant.
+fn gradient() -> Vec<u8> {
+    (0..16u8)
+        .flat_map(|y| (0..16u8).flat_map(move |x| [x * 17, y * 17, 128, 255]))
+        .collect()
+}
+
+/// Quantizes `pixels` and returns, for every channel of every pixel, how
+/// far the palette color it was mapped to is from the original.
+fn channel_errors(pixels: &[u8], max_colors: usize) -> Vec<u8> {
+    let (indices, table) = quantize(pixels, max_colors);
+    assert_eq!(indices.len(), pixels.len() / 4);
+    assert!(table.len() <= max_colors);
+
+    let colors: Vec<_> = table.iter().collect();
+    pixels
+        .chunks(4)
+        .zip(&indices)
+        .flat_map(|(pixel, &index)| {
+            let color = colors[index as usize];
+            [
+                pixel[0].abs_diff(color.red),
+                pixel[1].abs_diff(color.green),
+                pixel[2].abs_diff(color.blue),
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn a_palette_with_room_for_every_color_is_exact() {
+    assert!(channel_errors(&gradient(), 256)
+        .iter()
+        .all(|&error| error == 0));
+}
+
+#[test]
+fn a_smaller_palette_keeps_colors_close() {
+    //16 colors cover a 4 by 4 block of gradient steps, 17 apart, each. With
+    //the lab feature, matches are chosen by perceived difference, so single
+    //channels can be further off.
+    let errors = channel_errors(&gradient(), 16);
+    let mean = errors.iter().map(|&error| error as f64).sum::<f64>() / errors.len() as f64;
+    assert!(mean <= 17.0, "mean channel error {}", mean);
+}
+
+#[test]
+#[should_panic]
+fn max_colors_must_be_a_power_of_two() {
+    quantize(&gradient(), 100);
+}
@@ -0,0 +1,174 @@
+//! Converting decoded frames to RGBA.
+
+use gif::{Animator, ColorWithAlpha, Gif, GifError, GifSeeker};
+
+mod common;
+use common::fixture;
+
+const EARTH: &[u8] = include_bytes!("../earth.gif");
+
+const BLACK: ColorWithAlpha = ColorWithAlpha::new(0, 0, 0, 255);
+const WHITE: ColorWithAlpha = ColorWithAlpha::new(255, 255, 255, 255);
+const RED: ColorWithAlpha = ColorWithAlpha::new(255, 0, 0, 255);
+
+#[test]
+fn to_rgba_frames_looks_indices_up_in_the_palette() {
+    let frames = fixture("sample.gif").to_rgba_frames().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(
+        frames[0],
+        [BLACK, WHITE, RED, BLACK, WHITE, RED, BLACK, WHITE]
+    );
+    //the second frame only covers its own 2x1 rectangle.
+    assert_eq!(frames[1], [RED, RED]);
+}
+
+#[test]
+fn to_rgba_frames_makes_the_transparent_index_transparent() {
+    let mut gif = fixture("sample.gif");
+    let graphic_control = gif.frames[0].graphic_control.as_mut().unwrap();
+    graphic_control.transparent_color_index = Some(0);
+
+    let frames = gif.to_rgba_frames().unwrap();
+    let clear = ColorWithAlpha::TRANSPARENT_BLACK;
+    assert_eq!(
+        frames[0],
+        [clear, WHITE, RED, clear, WHITE, RED, clear, WHITE]
+    );
+}
+
+#[test]
+fn crop_cuts_a_rectangle_out_of_the_composited_canvas() {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    let canvas = GifSeeker::new(&gif).seek(5).unwrap();
+    let crop = gif.crop(5, 200, 120, 4, 4).unwrap();
+
+    assert_eq!(crop.len(), 4 * 4 * 4);
+    let width = gif.dimensions().0 as usize;
+    for (row, pixels) in crop.chunks(4 * 4).enumerate() {
+        let start = ((120 + row) * width + 200) * 4;
+        assert_eq!(pixels, &canvas[start..start + 4 * 4], "row {}", row);
+    }
+}
+
+#[test]
+fn crop_rejects_rectangles_past_the_canvas() {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    assert!(matches!(
+        gif.crop(0, 398, 0, 4, 4),
+        Err(GifError::OutOfBounds)
+    ));
+    assert!(matches!(
+        gif.crop(0, 0, 397, 4, 4),
+        Err(GifError::OutOfBounds)
+    ));
+    assert!(matches!(
+        gif.crop(44, 0, 0, 4, 4),
+        Err(GifError::OutOfBounds)
+    ));
+    assert!(gif.crop(0, 396, 396, 4, 4).is_ok());
+}
+
+#[test]
+fn thumbnail_rgba_samples_the_first_frame_down_to_max_side() {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    let canvas = GifSeeker::new(&gif).seek(0).unwrap();
+    let thumbnail = gif.thumbnail_rgba(100).unwrap();
+
+    assert_eq!(thumbnail.len(), 100 * 100 * 4);
+    assert_eq!(thumbnail[..4], canvas[..4]);
+    assert_eq!(gif.thumbnail_rgba(400).unwrap(), canvas);
+}
+
+#[test]
+fn thumbnail_rgba_of_a_zero_width_canvas_is_empty() {
+    let mut gif = fixture("sample.gif");
+    gif.lsd.width = 0;
+    assert_eq!(gif.thumbnail_rgba(1).unwrap(), []);
+}
+
+#[test]
+fn average_frame_color_of_a_solid_frame_is_its_color() {
+    let gif = fixture("three-frames.gif");
+    assert_eq!(
+        gif.average_frame_color(0).unwrap(),
+        gif::Color {
+            red: 255,
+            green: 0,
+            blue: 0
+        }
+    );
+}
+
+#[test]
+fn average_frame_color_rounds_the_mean_of_each_channel() {
+    //15 red pixels and 1 green one.
+    let gif = fixture("three-frames.gif");
+    assert_eq!(
+        gif.average_frame_color(1).unwrap(),
+        gif::Color::new(239, 16, 0)
+    );
+    assert!(matches!(
+        gif.average_frame_color(3),
+        Err(GifError::OutOfBounds)
+    ));
+}
+
+#[test]
+fn average_frame_color_leaves_out_transparent_pixels() {
+    //three black pixels turn transparent, leaving three white and two red.
+    let mut gif = fixture("sample.gif");
+    let graphic_control = gif.frames[0].graphic_control.as_mut().unwrap();
+    graphic_control.transparent_color_index = Some(0);
+    assert_eq!(
+        gif.average_frame_color(0).unwrap(),
+        gif::Color::new(255, 153, 153)
+    );
+}
+
+#[test]
+fn histogram_buckets_add_up_to_the_frame_size() {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    for (index, frame) in gif.frames.iter().enumerate() {
+        let histogram = gif.histogram(index).unwrap();
+        let size = frame.descriptor.width as u32 * frame.descriptor.height as u32;
+        assert_eq!(histogram.iter().sum::<u32>(), size, "frame {}", index);
+    }
+    assert!(matches!(gif.histogram(44), Err(GifError::OutOfBounds)));
+}
+
+#[test]
+fn histogram_counts_each_index() {
+    let gif = fixture("sample.gif");
+    let histogram = gif.histogram(0).unwrap();
+    assert_eq!(histogram[..4], [3, 3, 2, 0]);
+    assert!(!gif.histogram_has_invalid_indices(0));
+}
+
+#[test]
+fn histogram_counts_indices_past_the_color_table() {
+    let mut gif = fixture("still-87a.gif");
+    gif.frames[0].pixels[5] = 9;
+    let histogram = gif.histogram(0).unwrap();
+    assert_eq!(histogram[9], 1);
+    assert_eq!(histogram.iter().sum::<u32>(), 6);
+    assert!(gif.histogram_has_invalid_indices(0));
+}
+
+#[test]
+fn seek_composites_every_frame_up_to_the_index_in_any_order() {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    let mut seeker = GifSeeker::new(&gif);
+    for &index in &[0, 1, 3, 3, 2, 43, 0] {
+        let expected = Animator::new(&gif).render_frame(index).to_vec();
+        assert!(seeker.seek(index).unwrap() == expected, "frame {}", index);
+    }
+}
+
+#[test]
+fn seek_fails_past_the_last_frame() {
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    let mut seeker = GifSeeker::new(&gif);
+    assert!(matches!(seeker.seek(44), Err(GifError::OutOfBounds)));
+    assert!(seeker.seek(43).is_ok());
+}
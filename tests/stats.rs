@@ -0,0 +1,31 @@
+//! Summary statistics.
+
+use gif::Gif;
+
+mod common;
+use common::fixture;
+
+const EARTH: &[u8] = include_bytes!("../earth.gif");
+
+#[test]
+fn stats_agree_with_the_individual_accessors() {
+    for gif in [Gif::from_bytes(EARTH).unwrap(), fixture("sample.gif")] {
+        let stats = gif.stats();
+        assert_eq!(stats.frame_count, gif.frame_count());
+        assert_eq!(stats.canvas_width, gif.dimensions().0);
+        assert_eq!(stats.canvas_height, gif.dimensions().1);
+        assert_eq!(stats.has_transparency, gif.contains_transparency());
+        assert_eq!(stats.total_duration_ms, gif.total_duration_ms());
+    }
+}
+
+#[test]
+fn stats_of_a_known_animation() {
+    let stats = fixture("three-frames.gif").stats();
+    assert_eq!(stats.frame_count, 3);
+    assert_eq!(stats.total_pixels, 3 * 16);
+    assert_eq!(stats.unique_palette_entries, 4);
+    assert!(!stats.has_transparency);
+    assert_eq!(stats.total_duration_ms, 370.0 * 3.0);
+    assert!(stats.compressed_size_bytes > 0);
+}
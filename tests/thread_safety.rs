@@ -0,0 +1,66 @@
+//! Keeps the thread safety promised in the crate docs from regressing
+//! unnoticed: every public type is `Send + Sync`, and the decoder and
+//! encoder are whenever the reader or writer they wrap is.
+
+use gif::*;
+use static_assertions::assert_impl_all;
+
+assert_impl_all!(Gif: Send, Sync);
+assert_impl_all!(LogicalScreenDescriptor: Send, Sync);
+assert_impl_all!(Frame: Send, Sync);
+assert_impl_all!(ImageDescriptor: Send, Sync);
+assert_impl_all!(DiffResult: Send, Sync);
+assert_impl_all!(GifStats: Send, Sync);
+assert_impl_all!(DecodeOptions: Send, Sync);
+assert_impl_all!(GifVersion: Send, Sync);
+assert_impl_all!(GifError: Send, Sync);
+assert_impl_all!(Color: Send, Sync);
+assert_impl_all!(ColorTable: Send, Sync);
+assert_impl_all!(ColorWithAlpha: Send, Sync);
+assert_impl_all!(Palette: Send, Sync);
+assert_impl_all!(GraphicControlExtension: Send, Sync);
+assert_impl_all!(DisposalMethod: Send, Sync);
+assert_impl_all!(NetscapeExtension: Send, Sync);
+assert_impl_all!(PlainTextExtension: Send, Sync);
+assert_impl_all!(SubBlockReader<'static>: Send, Sync);
+assert_impl_all!(Animator<'static>: Send, Sync);
+assert_impl_all!(IntoIter: Send, Sync);
+assert_impl_all!(FrameIter<'static>: Send, Sync);
+assert_impl_all!(GifSeeker<'static>: Send, Sync);
+assert_impl_all!(GifBuilder: Send, Sync);
+assert_impl_all!(FrameBuilder: Send, Sync);
+assert_impl_all!(GifOptimizer: Send, Sync);
+assert_impl_all!(Block: Send, Sync);
+assert_impl_all!(ExtensionBlock: Send, Sync);
+assert_impl_all!(ImageBlock: Send, Sync);
+
+#[cfg(feature = "std")]
+mod std_types {
+    use gif::*;
+    use static_assertions::assert_impl_all;
+
+    assert_impl_all!(GifDecoder<&'static [u8]>: Send, Sync);
+    assert_impl_all!(GifEvent: Send, Sync);
+    assert_impl_all!(GifEncoder<Vec<u8>>: Send, Sync);
+    assert_impl_all!(GifInspector: Send, Sync);
+    assert_impl_all!(GifParser: Send, Sync);
+}
+
+#[cfg(feature = "wasm")]
+assert_impl_all!(WasmGif: Send, Sync);
+
+#[test]
+fn a_decoded_gif_can_be_shared_across_threads() {
+    let gif = std::sync::Arc::new(
+        Gif::from_bytes(include_bytes!("../fuzz/seeds/decode/animated.gif")).unwrap(),
+    );
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let gif = std::sync::Arc::clone(&gif);
+            std::thread::spawn(move || gif.frames.len())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 2);
+    }
+}
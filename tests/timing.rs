@@ -0,0 +1,46 @@
+//! Frame delays and animation length.
+
+mod common;
+use common::fixture;
+
+#[test]
+fn frame_delay_ms_converts_centiseconds() {
+    let gif = fixture("three-frames.gif");
+    assert_eq!(gif.frame_delay_ms(0), Some(100.0));
+    assert_eq!(gif.frame_delay_ms(1), Some(10.0));
+    assert_eq!(gif.frame_delay_ms(2), Some(250.0));
+    assert_eq!(gif.frame_delay_ms(3), None);
+}
+
+#[test]
+fn frame_delay_ms_normalized_raises_short_delays() {
+    let gif = fixture("three-frames.gif");
+    //the second frame is shown for 1 cs.
+    assert_eq!(gif.frame_delay_ms_normalized(1, 20.0), Some(20.0));
+    assert_eq!(gif.frame_delay_ms_normalized(0, 20.0), Some(100.0));
+    assert_eq!(gif.frame_delay_ms_normalized(3, 20.0), None);
+}
+
+#[test]
+fn frames_without_a_graphic_control_extension_have_no_delay() {
+    let gif = fixture("multi-image.gif");
+    assert_eq!(gif.frame_delay_ms(1), Some(0.0));
+    assert_eq!(gif.frame_delay_ms_normalized(1, 20.0), Some(20.0));
+}
+
+#[test]
+fn total_duration_ms_sums_the_normalized_delays_of_every_play() {
+    let mut gif = fixture("three-frames.gif");
+    //100ms, 10ms raised to 20ms, and 250ms, played once and repeated twice.
+    assert_eq!(gif.total_duration_ms(), 370.0 * 3.0);
+
+    gif.loop_count = None;
+    assert_eq!(gif.total_duration_ms(), 370.0);
+}
+
+#[test]
+fn total_duration_ms_is_infinite_for_a_looping_animation() {
+    let mut gif = fixture("three-frames.gif");
+    gif.loop_count = Some(0);
+    assert_eq!(gif.total_duration_ms(), f64::INFINITY);
+}
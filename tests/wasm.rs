@@ -0,0 +1,48 @@
+//! Exercises the JavaScript bindings in a WebAssembly runtime. Run with
+//! `wasm-pack test --node -- --features wasm --test wasm`.
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use gif::{Gif, WasmGif};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const EARTH: &[u8] = include_bytes!("../earth.gif");
+
+#[wasm_bindgen_test]
+fn from_bytes_reads_the_canvas_and_frames() {
+    let gif = WasmGif::from_bytes(EARTH).unwrap();
+    assert_eq!(gif.width(), 400);
+    assert_eq!(gif.height(), 400);
+    assert_eq!(gif.frame_count(), 44);
+}
+
+#[wasm_bindgen_test]
+fn from_bytes_rejects_data_that_is_not_a_gif() {
+    assert!(WasmGif::from_bytes(b"not a gif").is_err());
+}
+
+#[wasm_bindgen_test]
+fn frame_delay_ms_matches_the_decoder() {
+    let wasm = WasmGif::from_bytes(EARTH).unwrap();
+    let gif = Gif::from_bytes(EARTH).unwrap();
+    for index in 0..gif.frame_count() {
+        assert_eq!(
+            wasm.frame_delay_ms(index as u32),
+            gif.frame_delay_ms(index).unwrap()
+        );
+    }
+    assert_eq!(wasm.frame_delay_ms(44), 0.0);
+}
+
+#[wasm_bindgen_test]
+fn frame_rgba_returns_the_whole_canvas() {
+    let gif = WasmGif::from_bytes(EARTH).unwrap();
+    assert_eq!(gif.frame_rgba(0).unwrap().len(), 400 * 400 * 4);
+    assert_eq!(gif.frame_rgba(43).unwrap().len(), 400 * 400 * 4);
+}
+
+#[wasm_bindgen_test]
+fn frame_rgba_fails_past_the_last_frame() {
+    let gif = WasmGif::from_bytes(EARTH).unwrap();
+    assert!(gif.frame_rgba(44).is_err());
+}
@@ -0,0 +1,7 @@
+//! The `cdylib` that wasm-pack builds the JavaScript package from. All of
+//! the bindings live in the `gif` crate's `wasm` feature; this crate only
+//! links them into a WebAssembly module.
+
+#![deny(missing_docs)]
+
+pub use gif::WasmGif;